@@ -0,0 +1,194 @@
+use itertools::iproduct;
+use strum::IntoEnumIterator;
+
+use super::{Card, HAND_SIZE, Rank, Suit};
+use crate::game::players::PlayerId;
+
+/// The way cards are handed out to the players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DealPattern {
+    /// One card at a time to each player.
+    OneByOne,
+    /// Packets of four, then four, then five cards.
+    FourFourFive,
+    /// All thirteen cards at once to each player.
+    AllAtOnce,
+}
+
+impl DealPattern {
+    /// Size of each packet given to a player during one round of the deal.
+    #[must_use]
+    pub fn packets(&self) -> Vec<usize> {
+        match self {
+            Self::OneByOne => vec![1; HAND_SIZE],
+            Self::FourFourFive => vec![4, 4, 5],
+            Self::AllAtOnce => vec![HAND_SIZE],
+        }
+    }
+}
+
+/// A deck of 52 cards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deck {
+    /// Creates a sorted deck, by suit then by rank.
+    #[must_use]
+    pub fn new() -> Self {
+        let cards = iproduct!(Suit::iter(), Rank::iter())
+            .map(|(suit, rank)| Card::new(suit, rank))
+            .collect();
+        Self { cards }
+    }
+
+    /// Creates a deck shuffled with the given seed.
+    ///
+    /// The same seed always produces the same order, which allows to replay a deal.
+    #[must_use]
+    pub fn shuffled(seed: u64) -> Self {
+        let mut deck = Self::new();
+        deck.shuffle(seed);
+        deck
+    }
+
+    #[must_use]
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Shuffles the deck deterministically with the given seed.
+    pub fn shuffle(&mut self, seed: u64) {
+        let mut rng = SplitMix64(seed);
+        for i in (1..self.cards.len()).rev() {
+            let j = rng.below(i + 1);
+            self.cards.swap(i, j);
+        }
+    }
+
+    /// Moves the top `at` cards to the bottom of the deck.
+    pub fn cut(&mut self, at: usize) {
+        let at = at % self.cards.len().max(1);
+        self.cards.rotate_left(at);
+    }
+
+    /// Deals the deck into four hands of thirteen cards.
+    ///
+    /// The deal starts with `first` and goes around the table following the
+    /// `pattern`. The returned hands are indexed by player.
+    #[must_use]
+    pub fn deal(&self, pattern: &DealPattern, first: &PlayerId) -> [Vec<Card>; 4] {
+        let mut hands: [Vec<Card>; 4] = Default::default();
+        let mut cards = self.cards.iter().copied();
+        for packet in pattern.packets() {
+            for seat in 0..4 {
+                let player = (first.idx() + seat) % 4;
+                hands[player].extend(cards.by_ref().take(packet));
+            }
+        }
+        hands
+    }
+}
+
+/// Small deterministic pseudo-random generator.
+///
+/// Its output only depends on the seed, so shuffled decks stay reproducible
+/// whatever the platform or the crate version.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    const fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`.
+    #[allow(clippy::cast_possible_truncation)]
+    const fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn full_deck() {
+        let deck = Deck::new();
+        let unique: HashSet<_> = deck.cards().iter().collect();
+        assert_eq!(deck.cards().len(), 52);
+        assert_eq!(unique.len(), 52);
+    }
+
+    #[test]
+    fn seeded_shuffle() {
+        assert_eq!(Deck::shuffled(42), Deck::shuffled(42));
+        assert_ne!(Deck::shuffled(42), Deck::shuffled(43));
+        assert_ne!(Deck::shuffled(42), Deck::new());
+
+        let unique: HashSet<_> = Deck::shuffled(42).cards().iter().copied().collect();
+        assert_eq!(unique.len(), 52);
+    }
+
+    #[test]
+    fn deal_all_patterns() {
+        let deck = Deck::shuffled(7);
+        for pattern in [
+            DealPattern::OneByOne,
+            DealPattern::FourFourFive,
+            DealPattern::AllAtOnce,
+        ] {
+            let hands = deck.deal(&pattern, &PlayerId::new(0));
+            assert!(hands.iter().all(|h| h.len() == HAND_SIZE));
+
+            let unique: HashSet<_> = hands.iter().flatten().collect();
+            assert_eq!(unique.len(), 52);
+        }
+    }
+
+    #[test]
+    fn deal_four_four_five() {
+        let deck = Deck::new();
+        let cards = deck.cards();
+        let hands = deck.deal(&DealPattern::FourFourFive, &PlayerId::new(1));
+
+        assert_eq!(hands[1][..4], cards[..4]);
+        assert_eq!(hands[2][..4], cards[4..8]);
+        assert_eq!(hands[0][..4], cards[12..16]);
+        assert_eq!(hands[1][4..8], cards[16..20]);
+        assert_eq!(hands[0][8..], cards[47..]);
+    }
+
+    #[test]
+    fn deal_all_at_once() {
+        let deck = Deck::new();
+        let hands = deck.deal(&DealPattern::AllAtOnce, &PlayerId::new(3));
+
+        assert_eq!(hands[3], deck.cards()[..13]);
+        assert_eq!(hands[2], deck.cards()[39..]);
+    }
+
+    #[test]
+    fn cut() {
+        let mut deck = Deck::new();
+        let tenth = deck.cards()[10];
+        deck.cut(10);
+        assert_eq!(deck.cards()[0], tenth);
+        assert_eq!(deck.cards().len(), 52);
+    }
+}
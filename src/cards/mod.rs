@@ -0,0 +1,184 @@
+pub mod deck;
+pub mod play;
+
+pub use deck::{DealPattern, Deck};
+pub use play::{Play, PlayError, Trick};
+
+use std::fmt;
+
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    game::{contractors::Contractors, players::PlayerId},
+    gamemodes::TOTAL_TRICKS,
+};
+
+/// Number of cards dealt to each player.
+#[allow(clippy::cast_sign_loss)]
+pub const HAND_SIZE: usize = TOTAL_TRICKS as usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Self::Clubs => "♣",
+            Self::Diamonds => "♦",
+            Self::Hearts => "♥",
+            Self::Spades => "♠",
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum Rank {
+    Two = 2,
+    Three = 3,
+    Four = 4,
+    Five = 5,
+    Six = 6,
+    Seven = 7,
+    Eight = 8,
+    Nine = 9,
+    Ten = 10,
+    Jack = 11,
+    Queen = 12,
+    King = 13,
+    Ace = 14,
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Jack => write!(f, "J"),
+            Self::Queen => write!(f, "Q"),
+            Self::King => write!(f, "K"),
+            Self::Ace => write!(f, "A"),
+            rank => write!(f, "{}", *rank as u8),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Card {
+    pub suit: Suit,
+    pub rank: Rank,
+}
+
+impl Card {
+    #[must_use]
+    pub const fn new(suit: Suit, rank: Rank) -> Self {
+        Self { suit, rank }
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+/// Finds the troel contractors of a deal, if any.
+///
+/// The player holding three aces is partnered with the holder of the fourth
+/// ace. A player holding the four aces is partnered with the holder of the
+/// highest heart they do not hold.
+#[must_use]
+pub fn find_troel(hands: &[Vec<Card>; 4]) -> Option<Contractors> {
+    let holder_of = |card: Card| hands.iter().position(|h| h.contains(&card));
+    let aces = |hand: &Vec<Card>| hand.iter().filter(|c| c.rank == Rank::Ace).count();
+
+    let holder = hands.iter().position(|h| aces(h) >= 3)?;
+    let partner = if aces(&hands[holder]) == 3 {
+        Suit::iter()
+            .filter_map(|suit| holder_of(Card::new(suit, Rank::Ace)))
+            .find(|&p| p != holder)?
+    } else {
+        Rank::iter()
+            .rev()
+            .filter_map(|rank| holder_of(Card::new(Suit::Hearts, rank)))
+            .find(|&p| p != holder)?
+    };
+    Some(Contractors::Team(
+        PlayerId::new(holder),
+        PlayerId::new(partner),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hands_with(cards: [&[Card]; 4]) -> [Vec<Card>; 4] {
+        cards.map(<[Card]>::to_vec)
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Card::new(Suit::Hearts, Rank::Ace).to_string(), "A♥");
+        assert_eq!(Card::new(Suit::Clubs, Rank::Ten).to_string(), "10♣");
+        assert_eq!(Card::new(Suit::Spades, Rank::Two).to_string(), "2♠");
+    }
+
+    #[test]
+    fn rank_order() {
+        assert!(Rank::Ace > Rank::King);
+        assert!(Rank::Two < Rank::Three);
+    }
+
+    #[test]
+    fn troel_three_aces() {
+        let hands = hands_with([
+            &[Card::new(Suit::Hearts, Rank::King)],
+            &[
+                Card::new(Suit::Hearts, Rank::Ace),
+                Card::new(Suit::Clubs, Rank::Ace),
+                Card::new(Suit::Spades, Rank::Ace),
+            ],
+            &[Card::new(Suit::Diamonds, Rank::Two)],
+            &[Card::new(Suit::Diamonds, Rank::Ace)],
+        ]);
+        assert!(matches!(
+            find_troel(&hands),
+            Some(Contractors::Team(PlayerId(1), PlayerId(3)))
+        ));
+    }
+
+    #[test]
+    fn troel_four_aces() {
+        let hands = hands_with([
+            &[
+                Card::new(Suit::Hearts, Rank::Ace),
+                Card::new(Suit::Clubs, Rank::Ace),
+                Card::new(Suit::Spades, Rank::Ace),
+                Card::new(Suit::Diamonds, Rank::Ace),
+                Card::new(Suit::Hearts, Rank::King),
+            ],
+            &[Card::new(Suit::Hearts, Rank::Ten)],
+            &[Card::new(Suit::Hearts, Rank::Queen)],
+            &[Card::new(Suit::Clubs, Rank::King)],
+        ]);
+        assert!(matches!(
+            find_troel(&hands),
+            Some(Contractors::Team(PlayerId(0), PlayerId(2)))
+        ));
+    }
+
+    #[test]
+    fn no_troel() {
+        let hands = Deck::new().deal(&DealPattern::OneByOne, &PlayerId::new(0));
+        assert!(find_troel(&hands).is_none());
+    }
+}
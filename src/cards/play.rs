@@ -0,0 +1,525 @@
+use thiserror::Error;
+
+use super::{Card, Suit};
+use crate::game::{
+    contractors::Contractors,
+    hand::{HandBuildError, HandBuilder},
+    players::PlayerId,
+    table::Seating,
+};
+
+#[derive(Debug, Error)]
+pub enum PlayError {
+    #[error("It is not this player's turn")]
+    NotPlayerTurn,
+    #[error("This card is not in the player's hand")]
+    CardNotInHand,
+    #[error("The led suit must be followed")]
+    MustFollowSuit,
+    #[error("All tricks have already been played")]
+    Finished,
+    #[error("Some tricks have not been played yet")]
+    NotFinished,
+    #[error("The play requires four active players, not {0}")]
+    ActivePlayers(usize),
+    #[error("The leader does not take part in the play")]
+    UnknownLeader,
+    #[error(transparent)]
+    HandBuildError(#[from] HandBuildError),
+}
+
+/// Cards played during one trick, in playing order, with the player of each card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trick {
+    pub leader: PlayerId,
+    pub cards: Vec<(PlayerId, Card)>,
+}
+
+impl Trick {
+    const fn new(leader: PlayerId) -> Self {
+        Self {
+            leader,
+            cards: Vec::new(),
+        }
+    }
+
+    /// Suit of the first card of the trick.
+    #[must_use]
+    pub fn led_suit(&self) -> Option<Suit> {
+        self.cards.first().map(|(_, c)| c.suit)
+    }
+
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.cards.len() == 4
+    }
+
+    /// Returns the player who wins the trick, once the four cards have been played.
+    ///
+    /// The highest trump wins, or the highest card of the led suit if no trump
+    /// was played.
+    #[must_use]
+    pub fn winner(&self, trump: Option<Suit>) -> Option<PlayerId> {
+        if !self.is_complete() {
+            return None;
+        }
+        let led = self.led_suit()?;
+        let strength = |card: &Card| {
+            let suit_rank = if Some(card.suit) == trump {
+                2
+            } else {
+                u8::from(card.suit == led)
+            };
+            (suit_rank, card.rank)
+        };
+        let (winner, _) = self.cards.iter().max_by_key(|(_, card)| strength(card))?;
+        Some(winner.clone())
+    }
+}
+
+/// Trick-taking play of a dealt hand.
+///
+/// Cards are played one at a time, following the table order from the leader
+/// of each trick. The winner of a trick leads the next one.
+///
+/// Four players take part in the play, seated in table order. At a table of
+/// five or six, these are the players who do not sit out the hand. Tables with
+/// another number of active players, such as a table of three, cannot be
+/// played.
+#[derive(Debug, Clone)]
+pub struct Play {
+    seats: [PlayerId; 4],
+    hands: [Vec<Card>; 4],
+    trump: Option<Suit>,
+    current: Trick,
+    tricks: Vec<Trick>,
+    tricks_won: [i16; 4],
+}
+
+impl Play {
+    /// Starts the play of the dealt `hands` at a table of four, with `leader`
+    /// playing first.
+    ///
+    /// `trump` is `None` for contracts played without trump, such as misère.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `leader` is not one of the four players.
+    pub fn new(
+        hands: [Vec<Card>; 4],
+        trump: Option<Suit>,
+        leader: PlayerId,
+    ) -> Result<Self, PlayError> {
+        Self::with_seats([0, 1, 2, 3].map(PlayerId::new), hands, trump, leader)
+    }
+
+    /// Starts the play of the dealt `hands` between the players of `seats`.
+    ///
+    /// `hands[i]` is the hand of the player at `seats[i]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `leader` is not one of the players of `seats`.
+    pub fn with_seats(
+        seats: [PlayerId; 4],
+        hands: [Vec<Card>; 4],
+        trump: Option<Suit>,
+        leader: PlayerId,
+    ) -> Result<Self, PlayError> {
+        if !seats.contains(&leader) {
+            return Err(PlayError::UnknownLeader);
+        }
+        Ok(Self {
+            seats,
+            hands,
+            trump,
+            current: Trick::new(leader),
+            tricks: Vec::new(),
+            tricks_won: [0; 4],
+        })
+    }
+
+    /// Starts the play of the dealt `hands` between the active players of
+    /// `seating`, with its leader playing first.
+    ///
+    /// `hands[i]` is the hand of the `i`-th active player in table order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table does not have exactly four active players.
+    pub fn at_table(
+        seating: &Seating,
+        hands: [Vec<Card>; 4],
+        trump: Option<Suit>,
+    ) -> Result<Self, PlayError> {
+        let active = seating.active();
+        let count = active.len();
+        let seats = active
+            .try_into()
+            .map_err(|_| PlayError::ActivePlayers(count))?;
+        Self::with_seats(seats, hands, trump, seating.leader())
+    }
+
+    #[must_use]
+    pub const fn trump(&self) -> Option<Suit> {
+        self.trump
+    }
+
+    #[must_use]
+    pub const fn seats(&self) -> &[PlayerId] {
+        &self.seats
+    }
+
+    fn seat(&self, player: &PlayerId) -> Option<usize> {
+        self.seats.iter().position(|p| p == player)
+    }
+
+    /// Returns the cards left in the hand of `player`, which is empty if they
+    /// do not take part in the play.
+    #[must_use]
+    pub fn hand(&self, player: &PlayerId) -> &[Card] {
+        self.seat(player).map_or(&[], |seat| &self.hands[seat])
+    }
+
+    fn next_player(&self) -> PlayerId {
+        let leader = self.seat(&self.current.leader).unwrap_or(0);
+        self.seats[(leader + self.current.cards.len()) % 4].clone()
+    }
+
+    #[must_use]
+    pub const fn current_trick(&self) -> &Trick {
+        &self.current
+    }
+
+    #[must_use]
+    pub fn played_tricks(&self) -> &[Trick] {
+        &self.tricks
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.hands.iter().all(Vec::is_empty)
+    }
+
+    /// Returns the player who must play the next card, if any card is left.
+    #[must_use]
+    pub fn current_player(&self) -> Option<PlayerId> {
+        (!self.is_finished()).then(|| self.next_player())
+    }
+
+    /// Returns the cards `player` is allowed to play in the current trick.
+    ///
+    /// A player must follow the led suit when they can, and may play any card
+    /// otherwise.
+    #[must_use]
+    pub fn legal_cards(&self, player: &PlayerId) -> Vec<Card> {
+        let hand = self.hand(player);
+        let following: Vec<Card> = self
+            .current
+            .led_suit()
+            .map(|led| hand.iter().copied().filter(|c| c.suit == led).collect())
+            .unwrap_or_default();
+        if following.is_empty() {
+            hand.to_vec()
+        } else {
+            following
+        }
+    }
+
+    /// Plays `card` from the hand of `player`.
+    ///
+    /// Returns the winner of the trick when this card completes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all tricks have been played, if it is not the turn
+    /// of `player`, if the card is not in their hand or if they fail to follow
+    /// the led suit.
+    pub fn play(&mut self, player: &PlayerId, card: Card) -> Result<Option<PlayerId>, PlayError> {
+        if self.is_finished() {
+            return Err(PlayError::Finished);
+        }
+        if self.next_player() != *player {
+            return Err(PlayError::NotPlayerTurn);
+        }
+        let seat = self.seat(player).ok_or(PlayError::NotPlayerTurn)?;
+        let pos = self.hands[seat]
+            .iter()
+            .position(|c| *c == card)
+            .ok_or(PlayError::CardNotInHand)?;
+        if !self.legal_cards(player).contains(&card) {
+            return Err(PlayError::MustFollowSuit);
+        }
+
+        self.hands[seat].remove(pos);
+        self.current.cards.push((player.clone(), card));
+
+        let Some(winner) = self.current.winner(self.trump) else {
+            return Ok(None);
+        };
+        if let Some(seat) = self.seat(&winner) {
+            self.tricks_won[seat] += 1;
+        }
+        let trick = std::mem::replace(&mut self.current, Trick::new(winner.clone()));
+        self.tricks.push(trick);
+        Ok(Some(winner))
+    }
+
+    #[must_use]
+    pub fn tricks_won(&self, player: &PlayerId) -> i16 {
+        self.seat(player).map_or(0, |seat| self.tricks_won[seat])
+    }
+
+    /// Returns the number of tricks won by the contractors together.
+    #[must_use]
+    pub fn contractors_tricks(&self, contractors: &Contractors) -> i16 {
+        match contractors {
+            Contractors::Solo(id) => self.tricks_won(id),
+            Contractors::Team(id1, id2) => self.tricks_won(id1) + self.tricks_won(id2),
+            Contractors::Other(pias) => pias.iter().map(|p| self.tricks_won(&p.id)).sum(),
+        }
+    }
+
+    /// Sets the tricks of `hand` to the number of tricks won by its contractors.
+    ///
+    /// When the contract is declared in `Contractors::Other`, such as misère,
+    /// the tricks of each declarer are set individually, even for a lone
+    /// declarer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the play is not finished or if the contractors of the
+    /// hand have not been set.
+    pub fn set_hand_tricks(&self, hand: &mut HandBuilder) -> Result<(), PlayError> {
+        if !self.is_finished() {
+            return Err(PlayError::NotFinished);
+        }
+        let contractors = hand
+            .contractors()
+            .ok_or(HandBuildError::Contractors("No contractors"))?;
+        if let Contractors::Other(declarers) = contractors {
+            let declarers: Vec<PlayerId> = declarers.iter().map(|d| d.id.clone()).collect();
+            for declarer in declarers {
+                let tricks = self.tricks_won(&declarer);
+                hand.set_declarer_tricks(declarer, tricks)?;
+            }
+        } else {
+            let tricks = self.contractors_tricks(contractors);
+            hand.set_tricks(tricks);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::cards::{DealPattern, Deck, Rank};
+    use crate::game::contractors::ContractorsScore;
+    use crate::game::players::PlayerIdAndScore;
+    use crate::game::rules::{GameRules, select_rules};
+
+    use super::*;
+
+    const fn card(suit: Suit, rank: Rank) -> Card {
+        Card::new(suit, rank)
+    }
+
+    fn small_hands() -> [Vec<Card>; 4] {
+        [
+            vec![card(Suit::Hearts, Rank::Two), card(Suit::Spades, Rank::Ace)],
+            vec![card(Suit::Hearts, Rank::King), card(Suit::Clubs, Rank::Two)],
+            vec![
+                card(Suit::Clubs, Rank::Three),
+                card(Suit::Diamonds, Rank::Four),
+            ],
+            vec![card(Suit::Hearts, Rank::Ace), card(Suit::Spades, Rank::Two)],
+        ]
+    }
+
+    #[test]
+    fn trick_winner() {
+        let mut trick = Trick::new(PlayerId::new(2));
+        trick.cards = vec![
+            (PlayerId::new(2), card(Suit::Hearts, Rank::Ten)),
+            (PlayerId::new(3), card(Suit::Hearts, Rank::Ace)),
+            (PlayerId::new(0), card(Suit::Clubs, Rank::Two)),
+            (PlayerId::new(1), card(Suit::Spades, Rank::King)),
+        ];
+        assert_eq!(trick.winner(None), Some(PlayerId::new(3)));
+        assert_eq!(trick.winner(Some(Suit::Clubs)), Some(PlayerId::new(0)));
+        assert_eq!(trick.winner(Some(Suit::Diamonds)), Some(PlayerId::new(3)));
+
+        trick.cards.pop();
+        assert_eq!(trick.winner(None), None);
+    }
+
+    #[test]
+    fn follow_suit() {
+        let mut play = Play::new(small_hands(), Some(Suit::Clubs), PlayerId::new(0)).unwrap();
+        let err = play
+            .play(&PlayerId::new(1), card(Suit::Hearts, Rank::King))
+            .unwrap_err();
+        assert!(matches!(err, PlayError::NotPlayerTurn));
+
+        play.play(&PlayerId::new(0), card(Suit::Hearts, Rank::Two))
+            .unwrap();
+        let err = play
+            .play(&PlayerId::new(1), card(Suit::Clubs, Rank::Two))
+            .unwrap_err();
+        assert!(matches!(err, PlayError::MustFollowSuit));
+        let err = play
+            .play(&PlayerId::new(1), card(Suit::Hearts, Rank::Ace))
+            .unwrap_err();
+        assert!(matches!(err, PlayError::CardNotInHand));
+
+        play.play(&PlayerId::new(1), card(Suit::Hearts, Rank::King))
+            .unwrap();
+        // Player 2 has no heart and may trump.
+        assert_eq!(play.legal_cards(&PlayerId::new(2)).len(), 2);
+        play.play(&PlayerId::new(2), card(Suit::Clubs, Rank::Three))
+            .unwrap();
+        let winner = play
+            .play(&PlayerId::new(3), card(Suit::Hearts, Rank::Ace))
+            .unwrap();
+
+        assert_eq!(winner, Some(PlayerId::new(2)));
+        assert_eq!(play.current_player(), Some(PlayerId::new(2)));
+        assert_eq!(play.tricks_won(&PlayerId::new(2)), 1);
+    }
+
+    #[test]
+    fn full_play() {
+        let hands = Deck::shuffled(3).deal(&DealPattern::FourFourFive, &PlayerId::new(1));
+        let mut play = Play::new(hands, Some(Suit::Hearts), PlayerId::new(1)).unwrap();
+        while let Some(player) = play.current_player() {
+            let card = play.legal_cards(&player)[0];
+            play.play(&player, card).unwrap();
+        }
+
+        assert_eq!(play.played_tricks().len(), 13);
+        let total: i16 = (0..4).map(|i| play.tricks_won(&PlayerId::new(i))).sum();
+        assert_eq!(total, 13);
+        let err = play
+            .play(&PlayerId::new(0), card(Suit::Hearts, Rank::Two))
+            .unwrap_err();
+        assert!(matches!(err, PlayError::Finished));
+
+        let contract = Arc::new(select_rules(&GameRules::Dutch).remove(0));
+        let mut builder = HandBuilder::new(contract);
+        let err = play.set_hand_tricks(&mut builder).unwrap_err();
+        assert!(matches!(err, PlayError::HandBuildError(_)));
+
+        let contractors = Contractors::Team(PlayerId::new(0), PlayerId::new(2));
+        let expected = play.contractors_tricks(&contractors);
+        builder.set_contractors(contractors).unwrap();
+        play.set_hand_tricks(&mut builder).unwrap();
+        assert_eq!(builder.tricks(), expected);
+    }
+
+    #[test]
+    fn unfinished_play() {
+        let play = Play::new(small_hands(), None, PlayerId::new(0)).unwrap();
+        let contract = Arc::new(select_rules(&GameRules::Dutch).remove(1));
+        let mut builder = HandBuilder::new(contract);
+        builder
+            .set_contractors(Contractors::Solo(PlayerId::new(0)))
+            .unwrap();
+
+        let err = play.set_hand_tricks(&mut builder).unwrap_err();
+        assert!(matches!(err, PlayError::NotFinished));
+    }
+
+    #[test]
+    fn several_declarers_tricks() {
+        let hands = Deck::shuffled(11).deal(&DealPattern::OneByOne, &PlayerId::new(0));
+        let mut play = Play::new(hands, None, PlayerId::new(0)).unwrap();
+        while let Some(player) = play.current_player() {
+            let card = play.legal_cards(&player)[0];
+            play.play(&player, card).unwrap();
+        }
+
+        let contract = Arc::new(select_rules(&GameRules::Dutch).remove(2));
+        let mut builder = HandBuilder::new(contract);
+        builder
+            .set_contractors(Contractors::Other(vec![
+                PlayerIdAndScore::from_id(PlayerId::new(1)),
+                PlayerIdAndScore::from_id(PlayerId::new(3)),
+            ]))
+            .unwrap();
+        play.set_hand_tricks(&mut builder).unwrap();
+
+        let expected = [
+            (PlayerId::new(1), play.tricks_won(&PlayerId::new(1))),
+            (PlayerId::new(3), play.tricks_won(&PlayerId::new(3))),
+        ];
+        assert_eq!(builder.declarer_tricks(), expected);
+    }
+
+    #[test]
+    fn seated_play() {
+        let seats = [0, 2, 3, 4].map(PlayerId::new);
+        let hands = Deck::shuffled(5).deal(&DealPattern::OneByOne, &PlayerId::new(0));
+        let mut play = Play::with_seats(seats.clone(), hands, None, PlayerId::new(4)).unwrap();
+        assert_eq!(play.current_player(), Some(PlayerId::new(4)));
+        assert!(play.hand(&PlayerId::new(1)).is_empty());
+        let hands = Deck::shuffled(5).deal(&DealPattern::OneByOne, &PlayerId::new(0));
+        let err = Play::with_seats(seats.clone(), hands, None, PlayerId::new(1)).unwrap_err();
+        assert!(matches!(err, PlayError::UnknownLeader));
+
+        let card = play.legal_cards(&PlayerId::new(4))[0];
+        play.play(&PlayerId::new(4), card).unwrap();
+        assert_eq!(play.current_player(), Some(PlayerId::new(0)));
+
+        while let Some(player) = play.current_player() {
+            let card = play.legal_cards(&player)[0];
+            play.play(&player, card).unwrap();
+        }
+        let total: i16 = seats.iter().map(|p| play.tricks_won(p)).sum();
+        assert_eq!(total, 13);
+        assert_eq!(play.tricks_won(&PlayerId::new(1)), 0);
+    }
+
+    #[test]
+    fn table_play() {
+        let hands = || Deck::shuffled(5).deal(&DealPattern::OneByOne, &PlayerId::new(0));
+        let seating = Seating::new(5, PlayerId::new(1));
+        let play = Play::at_table(&seating, hands(), None).unwrap();
+        assert_eq!(play.seats(), [0, 2, 3, 4].map(PlayerId::new));
+        assert_eq!(play.current_player(), Some(PlayerId::new(2)));
+
+        let seating = Seating::new(3, PlayerId::new(0));
+        let err = Play::at_table(&seating, hands(), None).unwrap_err();
+        assert!(matches!(err, PlayError::ActivePlayers(3)));
+    }
+
+    #[test]
+    fn lone_misere_declarer() {
+        let hands = Deck::shuffled(11).deal(&DealPattern::OneByOne, &PlayerId::new(0));
+        let mut play = Play::new(hands, None, PlayerId::new(0)).unwrap();
+        while let Some(player) = play.current_player() {
+            let card = play.legal_cards(&player)[0];
+            play.play(&player, card).unwrap();
+        }
+
+        let contract = Arc::new(select_rules(&GameRules::Dutch).remove(2));
+        let mut builder = HandBuilder::new(contract);
+        builder
+            .set_contractors(Contractors::Other(vec![PlayerIdAndScore::from_id(
+                PlayerId::new(2),
+            )]))
+            .unwrap();
+        play.set_hand_tricks(&mut builder).unwrap();
+        let tricks = play.tricks_won(&PlayerId::new(2));
+        assert_eq!(builder.declarer_tricks(), [(PlayerId::new(2), tricks)]);
+
+        let hand = builder.build().unwrap();
+        let expected = if tricks == 0 { 12 } else { -24 };
+        assert!(matches!(
+            hand.get_contractors_score().unwrap(),
+            ContractorsScore::Other(declarers) if declarers[0].score == expected
+        ));
+    }
+}
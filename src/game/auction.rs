@@ -0,0 +1,566 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use super::{
+    contractors::{Contractors, ContractorsKind},
+    players::{PlayerId, PlayerIdAndScore},
+    rules::Contract,
+};
+
+#[derive(Debug, Error)]
+pub enum AuctionError {
+    #[error("It is not this player's turn")]
+    NotPlayerTurn,
+    #[error("This player does not take part in the auction")]
+    UnknownPlayer,
+    #[error("No contract with index {0}")]
+    UnknownContract(usize),
+    #[error("A forced contract cannot be bid")]
+    ForcedContract,
+    #[error("This contract cannot be bid with {0} tricks")]
+    InvalidTricks(i16),
+    #[error("The bid must be higher than the current one")]
+    TooLow,
+    #[error("The current bid cannot be joined")]
+    CannotJoin,
+    #[error("The auction is over")]
+    Finished,
+    #[error("The auction is not over")]
+    NotFinished,
+    #[error("Every player has passed")]
+    AllPassed,
+}
+
+/// A call made by a player during the auction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bid {
+    Pass,
+    /// Bids the contract at this index of the rule set, with its minimum number
+    /// of tricks. For a team contract such as Emballage, this is the "vraag".
+    Contract(usize),
+    /// Bids the contract at this index of the rule set, promising this number of
+    /// tricks. Only contracts with a maximum bid can be bid above their minimum.
+    Tricks(usize, i16),
+    /// Joins the highest bid: "mee" for a team contract, or another declarer for
+    /// a contract played by several players such as misère.
+    Join,
+}
+
+#[derive(Debug, Clone)]
+pub enum AuctionOutcome {
+    Contract {
+        contract_idx: usize,
+        contractors: Contractors,
+        /// Tricks promised, for a contract with a maximum bid.
+        bid: Option<i16>,
+    },
+    AllPass,
+}
+
+/// Auction deciding the contract of a hand.
+///
+/// The players taking part in the hand speak in turn, starting with `first`
+/// and following the order of `seats`. Contracts are ranked by their
+/// [`bid_rank`](Contract::bid_rank), and each trick bid above the minimum of a
+/// contract moves the bid one rank up: Emballage with 9 tricks beats Seul with
+/// 6 tricks, but not Seul with 7 tricks. A bid must rank above the current
+/// highest one. Forced contracts, such as Troel, cannot be bid.
+///
+/// The auction ends when every player has either passed or holds the highest
+/// bid. A player asking for a partner who is not joined must speak again: they
+/// may then bid a higher contract or pass.
+#[derive(Debug, Clone)]
+pub struct Auction {
+    contracts: Vec<Arc<Contract>>,
+    seats: Vec<PlayerId>,
+    next: Option<PlayerId>,
+    passed: Vec<PlayerId>,
+    highest: Option<HighestBid>,
+    bids: Vec<(PlayerId, Bid)>,
+}
+
+#[derive(Debug, Clone)]
+struct HighestBid {
+    contract_idx: usize,
+    tricks: Option<i16>,
+    holders: Vec<PlayerId>,
+}
+
+impl Auction {
+    /// Starts an auction between the players of `seats`, `first` speaking first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `first` is not one of the players of `seats`.
+    pub fn new(
+        contracts: &[Arc<Contract>],
+        seats: Vec<PlayerId>,
+        first: PlayerId,
+    ) -> Result<Self, AuctionError> {
+        if !seats.contains(&first) {
+            return Err(AuctionError::UnknownPlayer);
+        }
+        Ok(Self {
+            contracts: contracts.to_vec(),
+            seats,
+            next: Some(first),
+            passed: Vec::new(),
+            highest: None,
+            bids: Vec::new(),
+        })
+    }
+
+    /// Returns the player who must speak next, or `None` once the auction is over.
+    #[must_use]
+    pub const fn current_player(&self) -> Option<&PlayerId> {
+        self.next.as_ref()
+    }
+
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.next.is_none()
+    }
+
+    /// Every bid made so far, in order.
+    #[must_use]
+    pub fn bids(&self) -> &[(PlayerId, Bid)] {
+        &self.bids
+    }
+
+    /// Returns the index of the highest contract bid so far and its holders.
+    #[must_use]
+    pub fn highest(&self) -> Option<(usize, &[PlayerId])> {
+        self.highest
+            .as_ref()
+            .map(|h| (h.contract_idx, h.holders.as_slice()))
+    }
+
+    /// Returns the tricks promised by the highest bid, for a contract with a
+    /// maximum bid.
+    #[must_use]
+    pub fn highest_tricks(&self) -> Option<i16> {
+        self.highest.as_ref().and_then(|h| h.tricks)
+    }
+
+    /// Records the bid of `player`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the auction is over, if it is not the turn of
+    /// `player`, or if the bid is not allowed.
+    pub fn bid(&mut self, player: &PlayerId, bid: Bid) -> Result<(), AuctionError> {
+        let current = self.next.as_ref().ok_or(AuctionError::Finished)?;
+        if current != player {
+            return Err(AuctionError::NotPlayerTurn);
+        }
+
+        match bid {
+            Bid::Pass => {
+                self.passed.push(player.clone());
+                if let Some(highest) = &mut self.highest {
+                    highest.holders.retain(|h| h != player);
+                    if highest.holders.is_empty() {
+                        self.highest = None;
+                    }
+                }
+            }
+            Bid::Contract(idx) => {
+                let contract = self.biddable(idx)?;
+                let tricks = contract.max_bid.map(|_| contract.min_tricks());
+                self.raise(player, idx, tricks)?;
+            }
+            Bid::Tricks(idx, tricks) => {
+                let contract = self.biddable(idx)?;
+                let max_bid = contract
+                    .max_bid
+                    .ok_or(AuctionError::InvalidTricks(tricks))?;
+                if !(contract.min_tricks()..=max_bid).contains(&tricks) {
+                    return Err(AuctionError::InvalidTricks(tricks));
+                }
+                self.raise(player, idx, Some(tricks))?;
+            }
+            Bid::Join => {
+                let highest = self.highest.as_mut().ok_or(AuctionError::CannotJoin)?;
+                let can_join = match self.contracts[highest.contract_idx].contractors_kind {
+                    ContractorsKind::Solo => false,
+                    ContractorsKind::Team => highest.holders.len() == 1,
+                    ContractorsKind::Other => true,
+                };
+                if !can_join || highest.holders.contains(player) {
+                    return Err(AuctionError::CannotJoin);
+                }
+                highest.holders.push(player.clone());
+            }
+        }
+
+        self.bids.push((player.clone(), bid));
+        self.next = self.next_player(player);
+        Ok(())
+    }
+
+    fn biddable(&self, idx: usize) -> Result<&Contract, AuctionError> {
+        let contract = self
+            .contracts
+            .get(idx)
+            .ok_or(AuctionError::UnknownContract(idx))?;
+        if contract.is_forced() {
+            return Err(AuctionError::ForcedContract);
+        }
+        Ok(contract)
+    }
+
+    /// Makes `player` the holder of a new highest bid, if it ranks above the
+    /// current one.
+    fn raise(
+        &mut self,
+        player: &PlayerId,
+        contract_idx: usize,
+        tricks: Option<i16>,
+    ) -> Result<(), AuctionError> {
+        let rank = self.rank(contract_idx, tricks);
+        if self
+            .highest
+            .as_ref()
+            .is_some_and(|h| rank <= self.rank(h.contract_idx, h.tricks))
+        {
+            return Err(AuctionError::TooLow);
+        }
+        self.highest = Some(HighestBid {
+            contract_idx,
+            tricks,
+            holders: vec![player.clone()],
+        });
+        Ok(())
+    }
+
+    /// Returns the rank of a bid: the rank of its contract moved up by the
+    /// tricks bid above the minimum, then these extra tricks.
+    fn rank(&self, contract_idx: usize, tricks: Option<i16>) -> (usize, usize) {
+        let contract = &self.contracts[contract_idx];
+        let base = contract.bid_rank.map_or(contract_idx, usize::from);
+        let extra = tricks.map_or(0, |t| {
+            usize::try_from(t - contract.min_tricks()).unwrap_or_default()
+        });
+        (base + extra, extra)
+    }
+
+    fn is_holder(&self, player: &PlayerId) -> bool {
+        self.highest
+            .as_ref()
+            .is_some_and(|h| h.holders.contains(player))
+    }
+
+    fn next_player(&self, after: &PlayerId) -> Option<PlayerId> {
+        let pos = self.seats.iter().position(|p| p == after).unwrap_or(0);
+        let count = self.seats.len();
+        let mut active = (1..=count)
+            .map(|i| &self.seats[(pos + i) % count])
+            .filter(|p| !self.passed.contains(p));
+
+        if let Some(player) = active.find(|p| !self.is_holder(p)) {
+            return Some(player.clone());
+        }
+
+        // Only the holders are left: a lone player asking for a partner must decide
+        // to play a higher contract or to pass.
+        match &self.highest {
+            Some(h)
+                if self.contracts[h.contract_idx].contractors_kind == ContractorsKind::Team
+                    && h.holders.len() == 1 =>
+            {
+                Some(h.holders[0].clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the result of the auction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the auction is not over yet.
+    pub fn outcome(&self) -> Result<AuctionOutcome, AuctionError> {
+        if !self.is_finished() {
+            return Err(AuctionError::NotFinished);
+        }
+        let Some(highest) = &self.highest else {
+            return Ok(AuctionOutcome::AllPass);
+        };
+        let kind = self.contracts[highest.contract_idx].contractors_kind;
+        let contractors = match (kind, highest.holders.as_slice()) {
+            (ContractorsKind::Solo, [id]) => Contractors::Solo(id.clone()),
+            (ContractorsKind::Team, [id1, id2]) => Contractors::Team(id1.clone(), id2.clone()),
+            _ => Contractors::Other(
+                highest
+                    .holders
+                    .iter()
+                    .cloned()
+                    .map(PlayerIdAndScore::from_id)
+                    .collect(),
+            ),
+        };
+        Ok(AuctionOutcome::Contract {
+            contract_idx: highest.contract_idx,
+            contractors,
+            bid: highest.tricks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::rules::{GameRules, select_rules};
+
+    use super::*;
+
+    const EMBALLAGE: usize = 0;
+    const SEUL: usize = 1;
+    const PETITE_MISERE: usize = 2;
+    const ABONDANCE_9: usize = 5;
+    const TROEL: usize = 11;
+
+    fn new_auction(first: usize) -> Auction {
+        let contracts: Vec<Arc<Contract>> = select_rules(&GameRules::Dutch)
+            .into_iter()
+            .map(Arc::new)
+            .collect();
+        let seats = (0..4).map(PlayerId::new).collect();
+        Auction::new(&contracts, seats, PlayerId::new(first)).unwrap()
+    }
+
+    fn run(auction: &mut Auction, bids: &[(usize, Bid)]) {
+        for (player, bid) in bids {
+            auction.bid(&PlayerId::new(*player), *bid).unwrap();
+        }
+    }
+
+    #[test]
+    fn vraag_mee() {
+        let mut auction = new_auction(1);
+        run(
+            &mut auction,
+            &[
+                (1, Bid::Contract(EMBALLAGE)),
+                (2, Bid::Pass),
+                (3, Bid::Join),
+                (0, Bid::Pass),
+            ],
+        );
+
+        assert!(auction.is_finished());
+        let AuctionOutcome::Contract {
+            contract_idx,
+            contractors,
+            bid,
+        } = auction.outcome().unwrap()
+        else {
+            panic!("A contract was expected");
+        };
+        assert_eq!(contract_idx, EMBALLAGE);
+        assert_eq!(bid, Some(8));
+        assert!(matches!(
+            contractors,
+            Contractors::Team(PlayerId(1), PlayerId(3))
+        ));
+    }
+
+    #[test]
+    fn overbid() {
+        let mut auction = new_auction(0);
+        run(
+            &mut auction,
+            &[
+                (0, Bid::Contract(EMBALLAGE)),
+                (1, Bid::Join),
+                (2, Bid::Contract(SEUL)),
+                (3, Bid::Pass),
+            ],
+        );
+        // The team has been overbid and may answer.
+        assert_eq!(auction.current_player(), Some(&PlayerId::new(0)));
+        run(&mut auction, &[(0, Bid::Pass), (1, Bid::Pass)]);
+
+        let AuctionOutcome::Contract {
+            contract_idx,
+            contractors,
+            bid,
+        } = auction.outcome().unwrap()
+        else {
+            panic!("A contract was expected");
+        };
+        assert_eq!(contract_idx, SEUL);
+        assert_eq!(bid, Some(6));
+        assert!(matches!(contractors, Contractors::Solo(PlayerId(2))));
+    }
+
+    #[test]
+    fn too_low() {
+        let mut auction = new_auction(0);
+        run(&mut auction, &[(0, Bid::Contract(SEUL))]);
+
+        let err = auction
+            .bid(&PlayerId::new(1), Bid::Contract(EMBALLAGE))
+            .unwrap_err();
+        assert!(matches!(err, AuctionError::TooLow));
+        let err = auction.bid(&PlayerId::new(1), Bid::Join).unwrap_err();
+        assert!(matches!(err, AuctionError::CannotJoin));
+        let err = auction.bid(&PlayerId::new(2), Bid::Pass).unwrap_err();
+        assert!(matches!(err, AuctionError::NotPlayerTurn));
+        let err = auction
+            .bid(&PlayerId::new(1), Bid::Contract(42))
+            .unwrap_err();
+        assert!(matches!(err, AuctionError::UnknownContract(42)));
+    }
+
+    #[test]
+    fn several_misere_declarers() {
+        let mut auction = new_auction(3);
+        run(
+            &mut auction,
+            &[
+                (3, Bid::Contract(PETITE_MISERE)),
+                (0, Bid::Join),
+                (1, Bid::Pass),
+                (2, Bid::Join),
+            ],
+        );
+
+        let AuctionOutcome::Contract { contractors, .. } = auction.outcome().unwrap() else {
+            panic!("A contract was expected");
+        };
+        let Contractors::Other(declarers) = contractors else {
+            panic!("Several declarers were expected");
+        };
+        let ids: Vec<usize> = declarers.iter().map(|p| p.id.idx()).collect();
+        assert_eq!(ids, [3, 0, 2]);
+    }
+
+    #[test]
+    fn lone_vraag() {
+        let mut auction = new_auction(0);
+        run(
+            &mut auction,
+            &[
+                (0, Bid::Contract(EMBALLAGE)),
+                (1, Bid::Pass),
+                (2, Bid::Pass),
+                (3, Bid::Pass),
+            ],
+        );
+        assert_eq!(auction.current_player(), Some(&PlayerId::new(0)));
+        assert!(matches!(
+            auction.outcome().unwrap_err(),
+            AuctionError::NotFinished
+        ));
+
+        let mut alone = auction.clone();
+        run(&mut alone, &[(0, Bid::Contract(SEUL))]);
+        assert!(matches!(
+            alone.outcome().unwrap(),
+            AuctionOutcome::Contract {
+                contract_idx: SEUL,
+                ..
+            }
+        ));
+
+        run(&mut auction, &[(0, Bid::Pass)]);
+        assert!(matches!(
+            auction.outcome().unwrap(),
+            AuctionOutcome::AllPass
+        ));
+    }
+
+    #[test]
+    fn all_pass() {
+        let mut auction = new_auction(2);
+        run(
+            &mut auction,
+            &[
+                (2, Bid::Pass),
+                (3, Bid::Pass),
+                (0, Bid::Pass),
+                (1, Bid::Pass),
+            ],
+        );
+
+        assert!(matches!(
+            auction.outcome().unwrap(),
+            AuctionOutcome::AllPass
+        ));
+        let err = auction.bid(&PlayerId::new(2), Bid::Pass).unwrap_err();
+        assert!(matches!(err, AuctionError::Finished));
+    }
+
+    #[test]
+    fn trick_levels() {
+        let mut auction = new_auction(0);
+        run(
+            &mut auction,
+            &[
+                (0, Bid::Contract(SEUL)),
+                (1, Bid::Tricks(EMBALLAGE, 9)),
+                (2, Bid::Tricks(SEUL, 7)),
+            ],
+        );
+        let err = auction
+            .bid(&PlayerId::new(3), Bid::Tricks(EMBALLAGE, 9))
+            .unwrap_err();
+        assert!(matches!(err, AuctionError::TooLow));
+        let err = auction
+            .bid(&PlayerId::new(3), Bid::Tricks(SEUL, 9))
+            .unwrap_err();
+        assert!(matches!(err, AuctionError::InvalidTricks(9)));
+        let err = auction
+            .bid(&PlayerId::new(3), Bid::Tricks(PETITE_MISERE, 1))
+            .unwrap_err();
+        assert!(matches!(err, AuctionError::InvalidTricks(1)));
+
+        run(
+            &mut auction,
+            &[
+                (3, Bid::Tricks(EMBALLAGE, 10)),
+                (0, Bid::Join),
+                (1, Bid::Pass),
+                (2, Bid::Pass),
+            ],
+        );
+        assert_eq!(auction.highest_tricks(), Some(10));
+        assert!(matches!(
+            auction.outcome().unwrap(),
+            AuctionOutcome::Contract {
+                contract_idx: EMBALLAGE,
+                contractors: Contractors::Team(PlayerId(3), PlayerId(0)),
+                bid: Some(10),
+            }
+        ));
+    }
+
+    #[test]
+    fn contract_ranks() {
+        let mut auction = new_auction(0);
+        run(&mut auction, &[(0, Bid::Contract(ABONDANCE_9))]);
+        let err = auction
+            .bid(&PlayerId::new(1), Bid::Contract(SEUL))
+            .unwrap_err();
+        assert!(matches!(err, AuctionError::TooLow));
+        run(&mut auction, &[(1, Bid::Tricks(SEUL, 7))]);
+        let err = auction
+            .bid(&PlayerId::new(2), Bid::Contract(ABONDANCE_9))
+            .unwrap_err();
+        assert!(matches!(err, AuctionError::TooLow));
+        run(&mut auction, &[(2, Bid::Contract(PETITE_MISERE))]);
+        assert_eq!(auction.highest().unwrap().0, PETITE_MISERE);
+    }
+
+    #[test]
+    fn forced_contract() {
+        let mut auction = new_auction(0);
+        let err = auction
+            .bid(&PlayerId::new(0), Bid::Contract(TROEL))
+            .unwrap_err();
+        assert!(matches!(err, AuctionError::ForcedContract));
+        assert_eq!(auction.current_player(), Some(&PlayerId::new(0)));
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+
+use super::{
+    Game, GameError,
+    hand::{HandRecap, HistoryEntry, InputError},
+    players::PlayerId,
+    rules::Contract,
+};
+use crate::gamemodes::{GameResult, Score};
+
+/// How the score of the contractors of a hand is computed.
+///
+/// The tricks taken are first capped by the maximum bid of the contract,
+/// lowered by the number of tricks bid above the minimum and capped by the
+/// gamemode, giving the counted tricks. The gamemode turns them into base
+/// points, for making the contract exactly, plus points for each overtrick or
+/// undertrick. The points are then multiplied by the factor of the result and
+/// by the stake multiplier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreBreakdown {
+    pub gamemode_name: String,
+    pub bid: Option<i16>,
+    pub tricks: i16,
+    /// Number of tricks bid above the minimum of the contract.
+    pub bid_adjustment: i16,
+    /// Tricks used to score the contract.
+    pub counted_tricks: i16,
+    pub base_points: i16,
+    /// Points for the overtricks of a won contract, or the undertricks of a lost
+    /// one.
+    pub trick_points: i16,
+    pub result: GameResult,
+    pub result_multiplier: i16,
+    pub stake_multiplier: i16,
+    pub score: i16,
+}
+
+impl ScoreBreakdown {
+    pub(super) fn new(
+        contract: &Contract,
+        bid: Option<i16>,
+        tricks: i16,
+        stake_multiplier: i16,
+    ) -> Result<Self, InputError> {
+        let counted_tricks = contract.counted_tricks(bid, tricks);
+        let (base_points, _) = contract.gamemode.calculate_score(contract.min_tricks());
+        let (points, result) = contract.gamemode.calculate_score(counted_tricks);
+        let result_multiplier = contract.multipliers.factor(result);
+        let score = points
+            .checked_mul(result_multiplier)
+            .and_then(|points| points.checked_mul(stake_multiplier))
+            .ok_or(InputError::ScoreOverflow)?;
+        Ok(Self {
+            gamemode_name: contract.gamemode.name(),
+            bid,
+            tricks,
+            bid_adjustment: bid.map_or(0, |bid| bid - contract.min_tricks()),
+            counted_tricks,
+            base_points,
+            trick_points: points - base_points,
+            result,
+            result_multiplier,
+            stake_multiplier,
+            score,
+        })
+    }
+}
+
+impl fmt::Display for ScoreBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.gamemode_name)?;
+        if let Some(bid) = self.bid {
+            write!(f, ", bid {bid}")?;
+        }
+        write!(f, ", {} tricks", self.tricks)?;
+        if self.counted_tricks != self.tricks {
+            write!(f, " (counted as {})", self.counted_tricks)?;
+        }
+        write!(f, ": {} base points", self.base_points)?;
+        if self.trick_points != 0 {
+            let kind = match self.result {
+                GameResult::Lose => "undertricks",
+                GameResult::Win | GameResult::Capot => "overtricks",
+            };
+            write!(f, " + {} for {kind}", self.trick_points)?;
+        }
+        let result = match self.result {
+            GameResult::Win => "won",
+            GameResult::Lose => "lost",
+            GameResult::Capot => "capot",
+        };
+        write!(f, ", {result} x{}", self.result_multiplier)?;
+        if self.stake_multiplier != 1 {
+            write!(f, ", stakes x{}", self.stake_multiplier)?;
+        }
+        write!(f, " = {} points", self.score)
+    }
+}
+
+impl Game {
+    /// Returns how the score of the contractors of the committed hand at index
+    /// `hand_idx` was computed.
+    ///
+    /// Hands with independent declarers have one breakdown per declarer, given
+    /// by [`Game::declarer_breakdowns`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no played hand exists at the given index, or if the
+    /// hand has independent declarers.
+    pub fn breakdown(&self, hand_idx: usize) -> Result<ScoreBreakdown, GameError> {
+        let (recap, contract) = self.played_hand(hand_idx)?;
+        if !recap.declarer_tricks.is_empty() {
+            return Err(GameError::IndependentDeclarers(hand_idx));
+        }
+        Ok(ScoreBreakdown::new(
+            contract,
+            recap.bid,
+            recap.tricks,
+            recap.multiplier,
+        )?)
+    }
+
+    /// Returns how the score of each independent declarer of the committed hand
+    /// at index `hand_idx` was computed from their own tricks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no played hand exists at the given index.
+    pub fn declarer_breakdowns(
+        &self,
+        hand_idx: usize,
+    ) -> Result<Vec<(PlayerId, ScoreBreakdown)>, GameError> {
+        let (recap, contract) = self.played_hand(hand_idx)?;
+        recap
+            .declarer_tricks
+            .iter()
+            .map(|(id, tricks)| {
+                let breakdown =
+                    ScoreBreakdown::new(contract, recap.bid, *tricks, recap.multiplier)?;
+                Ok((id.clone(), breakdown))
+            })
+            .collect()
+    }
+
+    fn played_hand(&self, hand_idx: usize) -> Result<(&HandRecap, &Contract), GameError> {
+        let recap = self
+            .history
+            .get(hand_idx)
+            .and_then(HistoryEntry::recap)
+            .ok_or(GameError::UnknownHand(hand_idx))?;
+        let contract = self
+            .contracts
+            .get(recap.contract_idx)
+            .ok_or(GameError::UnknownContract(recap.contract_idx))?;
+        Ok((recap, contract))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::game::{
+        contractors::Contractors,
+        hand::HandBuilder,
+        players::{PlayerIdAndScore, Players},
+        rule_set::RuleSet,
+        rules::{GameRules, select_rules},
+    };
+
+    use super::*;
+
+    #[test]
+    fn emballage_with_bid() {
+        let contract = Arc::new(select_rules(&GameRules::Dutch).remove(0));
+        let mut builder = HandBuilder::new(contract);
+        builder
+            .set_contractors(Contractors::Team(PlayerId::new(0), PlayerId::new(1)))
+            .unwrap();
+        builder.set_bid(9).unwrap();
+        builder.set_tricks(11);
+        let mut hand = builder.build().unwrap();
+        hand.set_multiplier(2);
+
+        let breakdown = hand.get_score_breakdown().unwrap();
+        assert_eq!(breakdown.bid_adjustment, 1);
+        assert_eq!(breakdown.counted_tricks, 10);
+        assert_eq!(breakdown.base_points, 2);
+        assert_eq!(breakdown.trick_points, 2);
+        assert_eq!(breakdown.result, GameResult::Win);
+        assert_eq!(breakdown.score, hand.get_score().unwrap());
+        assert_eq!(
+            breakdown.to_string(),
+            "Emballage, bid 9, 11 tricks (counted as 10): 2 base points + 2 for overtricks, \
+             won x1, stakes x2 = 8 points"
+        );
+    }
+
+    #[test]
+    fn lost_seul() {
+        let contract = Arc::new(select_rules(&GameRules::Dutch).remove(1));
+        let mut builder = HandBuilder::new(contract);
+        builder
+            .set_contractors(Contractors::Solo(PlayerId::new(2)))
+            .unwrap();
+        builder.set_bid(6).unwrap();
+        builder.set_tricks(4);
+        let hand = builder.build().unwrap();
+
+        let breakdown = hand.get_score_breakdown().unwrap();
+        assert_eq!(breakdown.score, hand.get_score().unwrap());
+        assert_eq!(
+            breakdown.to_string(),
+            format!(
+                "Seul, bid 6, 4 tricks: {} base points + {} for undertricks, lost x-2 = {} points",
+                breakdown.base_points,
+                breakdown.trick_points,
+                hand.get_score().unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn seul_capped_by_gamemode() {
+        let mut contracts = select_rules(&GameRules::Dutch);
+        contracts[1].max_bid = None;
+        let rules = GameRules::Custom(RuleSet::new("House", contracts).unwrap());
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, rules).unwrap();
+        game.redeal(false);
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId::new(0)))
+            .unwrap();
+        hand.set_tricks(11);
+        game.commit_hand().unwrap();
+
+        let breakdown = game.breakdown(1).unwrap();
+        assert_eq!(breakdown.counted_tricks, 8);
+        assert_eq!(
+            breakdown.score,
+            game.history()[1].recap().unwrap().scores[0]
+        );
+        assert_eq!(
+            breakdown.to_string(),
+            "Seul, 11 tricks (counted as 8): 6 base points + 6 for overtricks, won x1 = 12 points"
+        );
+        assert!(game.declarer_breakdowns(1).unwrap().is_empty());
+        assert!(matches!(game.breakdown(0), Err(GameError::UnknownHand(0))));
+        assert!(matches!(game.breakdown(2), Err(GameError::UnknownHand(2))));
+    }
+
+    #[test]
+    fn committed_declarers() {
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+        let hand = game.start_hand(2).unwrap();
+        hand.set_contractors(Contractors::Other(vec![
+            PlayerIdAndScore::from_id(PlayerId::new(1)),
+            PlayerIdAndScore::from_id(PlayerId::new(3)),
+        ]))
+        .unwrap();
+        hand.set_declarer_tricks(PlayerId::new(1), 0).unwrap();
+        hand.set_declarer_tricks(PlayerId::new(3), 2).unwrap();
+        game.commit_hand().unwrap();
+
+        assert!(matches!(
+            game.breakdown(0),
+            Err(GameError::IndependentDeclarers(0))
+        ));
+        let breakdowns = game.declarer_breakdowns(0).unwrap();
+        assert_eq!(breakdowns.len(), 2);
+        assert_eq!(breakdowns[0].0, PlayerId::new(1));
+        assert_eq!(breakdowns[0].1.result, GameResult::Win);
+        assert_eq!(breakdowns[1].0, PlayerId::new(3));
+        assert_eq!(breakdowns[1].1.result, GameResult::Lose);
+    }
+}
@@ -0,0 +1,229 @@
+use super::{
+    Game,
+    contractors::Contractors,
+    hand::HistoryEntry,
+    players::{PlayerId, Players},
+};
+
+/// A hand-by-hand score sheet.
+///
+/// Each row holds the hand number, the gamemode name, the contractors, the bid,
+/// the tricks, then the score each player won or lost during the hand followed
+/// by the running totals. Passed deals are listed with the "Passed" gamemode.
+#[derive(Debug, Clone)]
+pub struct ScoreSheet {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl ScoreSheet {
+    #[must_use]
+    pub fn new(players: &Players, history: &[HistoryEntry]) -> Self {
+        let names = players.names();
+        let mut header: Vec<String> = ["Hand", "Gamemode", "Contractors", "Bid", "Tricks"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        header.extend(names.iter().cloned());
+        header.extend(names.iter().map(|name| format!("{name} total")));
+
+        let mut totals = vec![0; names.len()];
+        let rows = history
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let (mut row, scores) = match entry {
+                    HistoryEntry::Played(recap) => (
+                        vec![
+                            (i + 1).to_string(),
+                            recap.gamemode_name.clone(),
+                            contractors_names(players, &recap.contractors),
+                            recap.bid.map(|bid| bid.to_string()).unwrap_or_default(),
+                            recap.tricks.to_string(),
+                        ],
+                        recap.scores.clone(),
+                    ),
+                    HistoryEntry::Passed(_) => (
+                        vec![
+                            (i + 1).to_string(),
+                            "Passed".to_string(),
+                            String::new(),
+                            String::new(),
+                            String::new(),
+                        ],
+                        vec![0; names.len()],
+                    ),
+                };
+                for (total, score) in totals.iter_mut().zip(scores) {
+                    *total += score;
+                    row.push(score.to_string());
+                }
+                row.extend(totals.iter().map(ToString::to_string));
+                row
+            })
+            .collect();
+
+        Self { header, rows }
+    }
+
+    #[must_use]
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Renders the sheet as CSV, one line per hand after the header line.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        csv(&self.header, &self.rows)
+    }
+
+    /// Renders the sheet as a Markdown table.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        markdown(&self.header, &self.rows)
+    }
+}
+
+impl Game {
+    #[must_use]
+    pub fn score_sheet(&self) -> ScoreSheet {
+        ScoreSheet::new(&self.players, &self.history)
+    }
+}
+
+fn contractors_names(players: &Players, contractors: &Contractors) -> String {
+    let name = |id: &PlayerId| players.name(id).unwrap_or("?").to_string();
+    match contractors {
+        Contractors::Solo(id) => name(id),
+        Contractors::Team(id1, id2) => format!("{} & {}", name(id1), name(id2)),
+        Contractors::Other(pias) => pias
+            .iter()
+            .map(|p| name(&p.id))
+            .collect::<Vec<_>>()
+            .join(" & "),
+    }
+}
+
+/// Renders a table as CSV, one line per row after the header line.
+pub(super) fn csv(header: &[String], rows: &[Vec<String>]) -> String {
+    std::iter::once(header)
+        .chain(rows.iter().map(Vec::as_slice))
+        .map(|row| {
+            row.iter()
+                .map(|f| csv_field(f))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Renders a table as a Markdown table.
+pub(super) fn markdown(header: &[String], rows: &[Vec<String>]) -> String {
+    let line = |row: &[String]| {
+        let cells: Vec<String> = row.iter().map(|f| markdown_cell(f)).collect();
+        format!("| {} |\n", cells.join(" | "))
+    };
+    let separator = vec!["---".to_string(); header.len()];
+
+    let mut table = line(header);
+    table.push_str(&line(&separator));
+    for row in rows {
+        table.push_str(&line(row));
+    }
+    table
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escapes the pipes of a Markdown cell and replaces its line breaks, which
+/// would end the table row.
+fn markdown_cell(field: &str) -> String {
+    field
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{players::PlayerIdAndScore, rules::GameRules};
+
+    use super::*;
+
+    fn played_game() -> Game {
+        let players = Players::from_list(&["Ann", "Bob", "Cid", "Dan"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+
+        let hand = game.start_hand(0).unwrap();
+        hand.set_contractors(Contractors::Team(PlayerId(0), PlayerId(2)))
+            .unwrap();
+        hand.set_bid(9).unwrap();
+        hand.set_tricks(10);
+        game.commit_hand().unwrap();
+
+        let hand = game.start_hand(2).unwrap();
+        hand.set_contractors(Contractors::Other(vec![PlayerIdAndScore::new(
+            PlayerId(3),
+            12,
+        )]))
+        .unwrap();
+        game.commit_hand().unwrap();
+        game.redeal(false);
+        game
+    }
+
+    #[test]
+    fn csv() {
+        let csv = played_game().score_sheet().to_csv();
+        let expected = "\
+Hand,Gamemode,Contractors,Bid,Tricks,Ann,Bob,Cid,Dan,Ann total,Bob total,Cid total,Dan total
+1,Emballage,Ann & Cid,9,10,3,-3,3,-3,3,-3,3,-3
+2,Petite Misere,Dan,,0,-4,-4,-4,12,-1,-7,-1,9
+3,Passed,,,,0,0,0,0,-1,-7,-1,9
+";
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn markdown() {
+        let markdown = played_game().score_sheet().to_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("| Hand | Gamemode | Contractors |"));
+        assert!(lines[1].starts_with("| --- | --- |"));
+        assert_eq!(
+            lines[2],
+            "| 1 | Emballage | Ann & Cid | 9 | 10 | 3 | -3 | 3 | -3 | 3 | -3 | 3 | -3 |"
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("plain"), "plain");
+
+        let sheet = ScoreSheet {
+            header: vec!["a|b".to_string()],
+            rows: vec![],
+        };
+        assert_eq!(sheet.to_markdown(), "| a\\|b |\n| --- |\n");
+        assert_eq!(
+            markdown_cell("two\nlines\r\nhere\r"),
+            "two<br>lines<br>here<br>"
+        );
+    }
+}
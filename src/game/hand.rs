@@ -1,12 +1,16 @@
-use super::rules::Contract;
+use super::{
+    breakdown::ScoreBreakdown,
+    rules::{Contract, GameRules, Settlement, settle, settle_declarers},
+};
 use crate::{
     game::{
         contractors::{Contractors, ContractorsKind, ContractorsScore},
-        players::PlayerIdAndScore,
+        players::{PlayerId, PlayerIdAndScore},
+        table::{Seating, SitOutScoring},
     },
-    gamemodes::{Score, TOTAL_TRICKS},
+    gamemodes::{GameResult, Score, TOTAL_TRICKS},
 };
-use std::rc::Rc;
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -15,14 +19,18 @@ pub enum InputError {
     InvalidInput(&'static str),
     #[error("The score sum cannot be zero")]
     WrongScore,
+    #[error("The score is too large")]
+    ScoreOverflow,
 }
 
 #[derive(Debug)]
 pub struct Hand {
     pub contractors: Contractors,
-    contract: Rc<Contract>,
+    contract: Arc<Contract>,
     bid: Option<i16>,
     tricks: i16,
+    declarer_tricks: Vec<(PlayerId, i16)>,
+    multiplier: i16,
 }
 
 impl Hand {
@@ -32,46 +40,196 @@ impl Hand {
     }
 
     #[must_use]
-    pub fn get_score(&self) -> i16 {
-        let tricks = self
-            .contract
-            .max_bid
-            .map_or(self.tricks, |max| self.tricks.clamp(0, max));
-        let adjusted_tricks = self.bid.map_or(tricks, |bid| {
-            let diff = bid - self.contract.min_tricks();
-            tricks - diff
-        });
-        self.contract.gamemode.get_score(adjusted_tricks)
+    pub(crate) const fn contract(&self) -> &Arc<Contract> {
+        &self.contract
+    }
+
+    /// Returns the factor applied to every score of the hand, such as a doubled
+    /// stake after a passed deal.
+    #[must_use]
+    pub const fn multiplier(&self) -> i16 {
+        self.multiplier
     }
 
+    pub const fn set_multiplier(&mut self, multiplier: i16) {
+        self.multiplier = multiplier;
+    }
+
+    /// Returns `true` if each declarer of the hand wins or loses on their own,
+    /// with their individual tricks.
     #[must_use]
-    pub fn get_contractors_score(&self) -> ContractorsScore {
-        match &self.contractors {
+    pub const fn has_independent_declarers(&self) -> bool {
+        !self.declarer_tricks.is_empty()
+    }
+
+    /// Returns the score of the contractors, multiplied by the stake multiplier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the multiplied score is too large.
+    pub fn get_score(&self) -> Result<i16, InputError> {
+        Ok(self.get_score_breakdown()?.score)
+    }
+
+    /// Returns how the score of the contractors is computed from the tricks of
+    /// the hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the multiplied score is too large.
+    pub fn get_score_breakdown(&self) -> Result<ScoreBreakdown, InputError> {
+        self.breakdown(self.tricks)
+    }
+
+    /// Returns how the score of each independent declarer is computed from
+    /// their own tricks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the multiplied score of a declarer is too large.
+    pub fn declarer_breakdowns(&self) -> Result<Vec<(PlayerId, ScoreBreakdown)>, InputError> {
+        self.declarer_tricks
+            .iter()
+            .map(|(id, tricks)| Ok((id.clone(), self.breakdown(*tricks)?)))
+            .collect()
+    }
+
+    fn breakdown(&self, tricks: i16) -> Result<ScoreBreakdown, InputError> {
+        ScoreBreakdown::new(&self.contract, self.bid, tricks, self.multiplier)
+    }
+
+    /// Returns the result of the hand.
+    ///
+    /// With independent declarers, the hand is only won if every declarer wins.
+    #[must_use]
+    pub fn get_result(&self) -> GameResult {
+        let result = |tricks| {
+            let (_, result) = self
+                .contract
+                .gamemode
+                .calculate_score(self.contract.counted_tricks(self.bid, tricks));
+            result
+        };
+        if !self.has_independent_declarers() {
+            return result(self.tricks);
+        }
+        self.declarer_tricks
+            .iter()
+            .map(|(_, tricks)| result(*tricks))
+            .find(|r| *r == GameResult::Lose)
+            .unwrap_or(GameResult::Win)
+    }
+
+    /// Returns the score of each contractor.
+    ///
+    /// With independent declarers, each declarer is scored with their own tricks.
+    /// Otherwise, the scores of `Contractors::Other` are the ones provided by the
+    /// caller. Every score is multiplied by the stake multiplier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a multiplied score is too large.
+    pub fn get_contractors_score(&self) -> Result<ContractorsScore, InputError> {
+        if self.has_independent_declarers() {
+            return self
+                .declarer_tricks
+                .iter()
+                .map(|(id, tricks)| {
+                    Ok(PlayerIdAndScore::new(
+                        id.clone(),
+                        self.breakdown(*tricks)?.score,
+                    ))
+                })
+                .collect::<Result<_, _>>()
+                .map(ContractorsScore::Other);
+        }
+        Ok(match &self.contractors {
             Contractors::Solo(id) => {
-                let score = self.get_score();
+                let score = self.get_score()?;
                 ContractorsScore::Solo(PlayerIdAndScore::new(id.clone(), score))
             }
             Contractors::Team(id1, id2) => {
-                let score = self.get_score();
+                let score = self.get_score()?;
                 ContractorsScore::Team(
                     PlayerIdAndScore::new(id1.clone(), score),
                     PlayerIdAndScore::new(id2.clone(), score),
                 )
             }
-            Contractors::Other(player_id_and_scores) => {
-                ContractorsScore::Other(player_id_and_scores.clone())
+            Contractors::Other(player_id_and_scores) => ContractorsScore::Other(
+                player_id_and_scores
+                    .iter()
+                    .map(|p| {
+                        let score = p
+                            .score
+                            .checked_mul(self.multiplier)
+                            .ok_or(InputError::ScoreOverflow)?;
+                        Ok(PlayerIdAndScore::new(p.id.clone(), score))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+
+    /// Returns the score won or lost by each player of the table during this hand.
+    ///
+    /// Independent declarers are settled one by one against the other players,
+    /// and their settlements are added up. Players sitting out are scored
+    /// according to `sit_out`, and the defenders pay according to `settlement`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contractors score is too large or cannot be split
+    /// between the players, or if a contractor sits out the hand.
+    pub fn get_players_score(
+        &self,
+        seating: &Seating,
+        sit_out: SitOutScoring,
+        settlement: Settlement,
+    ) -> Result<Vec<i16>, InputError> {
+        match self.get_contractors_score()? {
+            ContractorsScore::Other(declarers) if self.has_independent_declarers() => {
+                settle_declarers(&declarers, seating, sit_out, settlement)
             }
+            contractors => settle(&contractors, seating, sit_out, settlement),
         }
     }
 
-    #[must_use]
-    pub fn as_recap(self, scores: [i16; 4]) -> HandRecap {
-        HandRecap {
+    /// Turns the hand into a recap, given the players scores, the rule set and
+    /// the position of its contract in it, and the seating of the hand.
+    ///
+    /// With independent declarers, the recap contractors hold the score of each
+    /// declarer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the score of a declarer is too large.
+    pub fn as_recap(
+        self,
+        scores: Vec<i16>,
+        rules: &GameRules,
+        contract_idx: usize,
+        seating: &Seating,
+    ) -> Result<HandRecap, InputError> {
+        let contractors = match self.get_contractors_score()? {
+            ContractorsScore::Other(declarers) if self.has_independent_declarers() => {
+                Contractors::Other(declarers)
+            }
+            _ => self.contractors.clone(),
+        };
+        Ok(HandRecap {
             scores,
             gamemode_name: self.gamemode_name(),
+            result: self.get_result(),
+            rules_name: rules.name().to_string(),
+            contract_idx,
             tricks: self.tricks,
             bid: self.bid,
-        }
+            contractors,
+            declarer_tricks: self.declarer_tricks,
+            multiplier: self.multiplier,
+            dealer: seating.dealer().clone(),
+            sitting_out: seating.sitting_out().to_vec(),
+        })
     }
 }
 
@@ -80,30 +238,72 @@ pub enum InputRequest {
     ContractorsSolo,
     ContractorsTeam,
     ContractorsOther,
-    Bid { min: i16, max: i16 },
+    Bid {
+        min: i16,
+        max: i16,
+    },
+    /// Tricks won by this declarer, who wins or loses on their own.
+    DeclarerTricks(PlayerId),
     Done,
     Cancel,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HandBuilder {
-    contract: Rc<Contract>,
+    contract: Arc<Contract>,
     contractors: Option<Contractors>,
     bid: Option<i16>,
     tricks: i16,
+    declarer_tricks: Vec<(PlayerId, i16)>,
+    independent_declarers: bool,
 }
 
 impl HandBuilder {
     #[must_use]
-    pub const fn new(contract: Rc<Contract>) -> Self {
+    pub const fn new(contract: Arc<Contract>) -> Self {
         Self {
             contract,
             contractors: None,
             bid: None,
             tricks: 0,
+            declarer_tricks: Vec::new(),
+            independent_declarers: false,
         }
     }
 
+    #[must_use]
+    pub const fn contract(&self) -> &Arc<Contract> {
+        &self.contract
+    }
+
+    #[must_use]
+    pub const fn contractors(&self) -> Option<&Contractors> {
+        self.contractors.as_ref()
+    }
+
+    #[must_use]
+    pub const fn bid(&self) -> Option<i16> {
+        self.bid
+    }
+
+    #[must_use]
+    pub const fn tricks(&self) -> i16 {
+        self.tricks
+    }
+
+    #[must_use]
+    pub fn declarer_tricks(&self) -> &[(PlayerId, i16)] {
+        &self.declarer_tricks
+    }
+
+    /// Returns `true` if the declarers were set with
+    /// [`HandBuilder::set_independent_declarers`], so that the tricks of each of
+    /// them are required.
+    #[must_use]
+    pub const fn has_independent_declarers(&self) -> bool {
+        self.independent_declarers
+    }
+
     #[must_use]
     pub fn all_requests(&self) -> Vec<InputRequest> {
         let mut requests = vec![self.contract_request()];
@@ -124,9 +324,26 @@ impl HandBuilder {
         {
             return req;
         }
+        if let Some(declarer) = self.missing_declarer_tricks() {
+            return InputRequest::DeclarerTricks(declarer.clone());
+        }
         InputRequest::Done
     }
 
+    /// Returns an independent declarer whose tricks have not been set yet.
+    fn missing_declarer_tricks(&self) -> Option<&PlayerId> {
+        let Some(Contractors::Other(declarers)) = &self.contractors else {
+            return None;
+        };
+        if !self.independent_declarers && self.declarer_tricks.is_empty() {
+            return None;
+        }
+        declarers
+            .iter()
+            .map(|d| &d.id)
+            .find(|id| !self.declarer_tricks.iter().any(|(p, _)| p == *id))
+    }
+
     fn contract_request(&self) -> InputRequest {
         match self.contract.contractors_kind {
             ContractorsKind::Solo => InputRequest::ContractorsSolo,
@@ -161,6 +378,40 @@ impl HandBuilder {
         }
 
         self.contractors = Some(c);
+        self.declarer_tricks.clear();
+        self.independent_declarers = false;
+        Ok(())
+    }
+
+    /// Sets declarers who each win or lose on their own with their own tricks,
+    /// such as the misère declarers won at an auction.
+    ///
+    /// The tricks of every declarer must then be set with
+    /// [`HandBuilder::set_declarer_tricks`] before the hand can be built.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract is not played by `Contractors::Other`,
+    /// or if no declarer is given.
+    pub fn set_independent_declarers(
+        &mut self,
+        declarers: Vec<PlayerId>,
+    ) -> Result<(), HandBuildError> {
+        if self.contract.contractors_kind != ContractorsKind::Other {
+            return Err(HandBuildError::Contractors(
+                "Contractors type does not match",
+            ));
+        }
+        if declarers.is_empty() {
+            return Err(HandBuildError::Contractors("No declarers"));
+        }
+        self.set_contractors(Contractors::Other(
+            declarers
+                .into_iter()
+                .map(PlayerIdAndScore::from_id)
+                .collect(),
+        ))?;
+        self.independent_declarers = true;
         Ok(())
     }
 
@@ -188,15 +439,58 @@ impl HandBuilder {
         self.tricks = tricks.clamp(0, TOTAL_TRICKS);
     }
 
+    /// Sets the tricks won by one of several declarers, such as in misère.
+    ///
+    /// Once the tricks of every declarer are set, each declarer wins or loses on
+    /// their own and the hand is scored automatically, without providing the
+    /// score of each declarer in `Contractors::Other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contractors are not `Contractors::Other`, or if
+    /// `player` is not one of them.
+    pub fn set_declarer_tricks(
+        &mut self,
+        player: PlayerId,
+        tricks: i16,
+    ) -> Result<(), HandBuildError> {
+        let Some(Contractors::Other(declarers)) = &self.contractors else {
+            return Err(HandBuildError::Contractors(
+                "Individual tricks require declarers in Contractors::Other",
+            ));
+        };
+        if !declarers.iter().any(|d| d.id == player) {
+            return Err(HandBuildError::Contractors("This player is not a declarer"));
+        }
+
+        let tricks = tricks.clamp(0, TOTAL_TRICKS);
+        if let Some(entry) = self
+            .declarer_tricks
+            .iter_mut()
+            .find(|(id, _)| *id == player)
+        {
+            entry.1 = tricks;
+        } else {
+            self.declarer_tricks.push((player, tricks));
+        }
+        self.tricks = self.declarer_tricks.iter().map(|(_, t)| t).sum();
+        Ok(())
+    }
+
     /// Builds the hand from the collected contract parameters.
     ///
     /// All required components must be set before building the hand.
     ///
     /// # Errors
     ///
-    /// Returns an error if the contractors are missing, or if a bid is required
-    /// by the contract but has not been set.
+    /// Returns an error if the contractors are missing, if a bid is required
+    /// by the contract but has not been set, or if the tricks of some of the
+    /// declarers are missing, either because only some of them have been set or
+    /// because the declarers were set as independent declarers.
     pub fn build(self) -> Result<Hand, HandBuildError> {
+        if self.missing_declarer_tricks().is_some() {
+            return Err(HandBuildError::Contractors("Missing tricks for a declarer"));
+        }
         let contractors = self
             .contractors
             .ok_or(HandBuildError::Contractors("No contractors"))?;
@@ -208,6 +502,8 @@ impl HandBuilder {
             contractors,
             bid: self.bid,
             tricks: self.tricks,
+            declarer_tricks: self.declarer_tricks,
+            multiplier: 1,
         })
     }
 }
@@ -220,12 +516,70 @@ pub enum HandBuildError {
     Bid(&'static str),
 }
 
+/// Everything that is known about a committed hand.
+///
+/// `scores` holds the score each player of the table won or lost during this
+/// hand.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HandRecap {
-    pub scores: [i16; 4],
+    pub scores: Vec<i16>,
     pub gamemode_name: String,
+    pub result: GameResult,
+    /// Name of the rule set the hand was played with.
+    pub rules_name: String,
+    pub contract_idx: usize,
     pub tricks: i16,
-    // pub contractors: ContractorsScore,
+    pub contractors: Contractors,
     pub bid: Option<i16>,
+    /// Tricks won by each declarer, when they were scored independently.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub declarer_tricks: Vec<(PlayerId, i16)>,
+    /// Factor applied to the scores of the hand.
+    pub multiplier: i16,
+    pub dealer: PlayerId,
+    /// Players who did not take part in the hand.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sitting_out: Vec<PlayerId>,
+}
+
+/// A deal where every player passed, so that no contract was played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PassedHand {
+    pub dealer: PlayerId,
+    /// `true` if the same player dealt again after this deal.
+    pub dealer_kept: bool,
+}
+
+/// An entry of the game history: a played hand or a passed deal.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HistoryEntry {
+    Played(HandRecap),
+    Passed(PassedHand),
+}
+
+impl HistoryEntry {
+    /// Returns the recap of a played hand, or `None` for a passed deal.
+    #[must_use]
+    pub const fn recap(&self) -> Option<&HandRecap> {
+        match self {
+            Self::Played(recap) => Some(recap),
+            Self::Passed(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_passed(&self) -> bool {
+        matches!(self, Self::Passed(_))
+    }
+
+    #[must_use]
+    pub const fn dealer(&self) -> &PlayerId {
+        match self {
+            Self::Played(recap) => &recap.dealer,
+            Self::Passed(passed) => &passed.dealer,
+        }
+    }
 }
@@ -1,11 +1,27 @@
+pub mod auction;
+pub mod breakdown;
 pub mod contractors;
+pub mod export;
 pub mod hand;
 pub mod players;
+pub mod rule_set;
 pub mod rules;
+#[cfg(feature = "serde")]
+pub mod save;
+pub mod session;
+pub mod stats;
+pub mod table;
+pub mod validation;
+
+pub use session::Game;
 
 use thiserror::Error;
 
-use crate::game::hand::{HandBuildError, InputError};
+use crate::game::{
+    auction::AuctionError,
+    hand::{HandBuildError, InputError},
+    rule_set::RuleSetError,
+};
 
 #[derive(Debug, Error)]
 pub enum GameError {
@@ -13,8 +29,26 @@ pub enum GameError {
     TooManyPlayer,
     #[error("This player name already exists")]
     PlayerAlreadyExists,
+    #[error("A game requires three to six players")]
+    NotEnoughPlayers,
+    #[error("This player is not part of the game")]
+    UnknownPlayer,
+    #[error("No contract with index {0}")]
+    UnknownContract(usize),
+    #[error("No hand with index {0}")]
+    UnknownHand(usize),
+    #[error("The hand at index {0} has one breakdown per declarer")]
+    IndependentDeclarers(usize),
+    #[error("The hand contract does not belong to this game")]
+    ForeignContract,
+    #[error("No hand is in progress")]
+    NoHandInProgress,
     #[error(transparent)]
     HandBuildError(#[from] HandBuildError),
     #[error(transparent)]
     InputError(#[from] InputError),
+    #[error(transparent)]
+    AuctionError(#[from] AuctionError),
+    #[error(transparent)]
+    RuleSetError(#[from] RuleSetError),
 }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use super::GameError;
+use super::{GameError, table::MAX_PLAYERS};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -83,7 +83,7 @@ pub struct Players {
 }
 
 impl Players {
-    /// Creates a `Players` collection from a fixed list of four player names.
+    /// Creates a `Players` collection from a list of player names.
     ///
     /// The provided values are converted to strings and added in order using the
     /// standard validation rules.
@@ -92,7 +92,7 @@ impl Players {
     ///
     /// Returns an error if adding any player fails, for example if a name is
     /// duplicated or if the maximum number of players is exceeded.
-    pub fn from_list(names: &[impl std::string::ToString; 4]) -> Result<Self, GameError> {
+    pub fn from_list(names: &[impl std::string::ToString]) -> Result<Self, GameError> {
         let mut players = Self::default();
         for n in names {
             players.add_player(n.to_string())?;
@@ -103,15 +103,15 @@ impl Players {
     /// Adds a new player to the game.
     ///
     /// A player is created with the given name and assigned a unique internal
-    /// identifier. The game must contain four players. Returns the number of players.
+    /// identifier. A table holds up to six players. Returns the number of players.
     ///
     /// # Errors
     ///
-    /// Returns an `GameError` if the game already has four players or if the player
+    /// Returns an `GameError` if the table is already full or if the player
     /// already exists.
     pub fn add_player(&mut self, name: String) -> Result<usize, GameError> {
         let player = Player::new(name.clone(), self.next_idx);
-        if self.list.len() >= 4 {
+        if self.list.len() >= MAX_PLAYERS {
             return Err(GameError::TooManyPlayer);
         } else if self.name_to_id.keys().contains(&name) {
             return Err(GameError::PlayerAlreadyExists);
@@ -127,6 +127,14 @@ impl Players {
         self.name_to_id.get(name).cloned()
     }
 
+    #[must_use]
+    pub fn name(&self, id: &PlayerId) -> Option<&str> {
+        self.list
+            .iter()
+            .find(|p| p.id == *id)
+            .map(|p| p.name.as_str())
+    }
+
     #[must_use]
     pub fn names(&self) -> Vec<String> {
         self.list.iter().map(|p| p.name.clone()).collect()
@@ -143,14 +151,22 @@ impl Players {
     /// that the score sum is not zero
     /// (for example, an impossible amount of players has been supplied).
     #[allow(clippy::missing_panics_doc)]
-    pub fn update_score(&mut self, scores: &[i16; 4]) {
+    pub fn update_score(&mut self, scores: &[i16]) {
         for (player, score) in self.list.iter_mut().zip(scores.iter()) {
             player.score += score;
         }
     }
+
+    /// Resets every player score to zero.
+    pub fn reset_scores(&mut self) {
+        for player in &mut self.list {
+            player.score = 0;
+        }
+    }
 }
 
 #[cfg(test)]
+#[allow(unused_mut)]
 mod tests {
     use crate::game::contractors::ContractorsScore;
     use crate::game::hand::InputError;
@@ -310,4 +326,14 @@ mod tests {
         let err = calculate_players_score(&contractors).unwrap_err();
         assert!(matches!(err, InputError::WrongScore));
     }
+
+    #[test]
+    fn table_size() {
+        let mut players = Players::from_list(&["A", "B", "C", "D", "E", "F"]).unwrap();
+        let err = players.add_player("G".to_string()).unwrap_err();
+        assert!(matches!(err, GameError::TooManyPlayer));
+
+        let players = Players::from_list(&["A", "B", "C"]).unwrap();
+        assert_eq!(players.names(), ["A", "B", "C"]);
+    }
 }
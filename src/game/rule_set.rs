@@ -0,0 +1,314 @@
+#[cfg(feature = "serde")]
+use std::{fs, io, path::Path};
+
+use thiserror::Error;
+
+use super::{contractors::ContractorsKind, rules::Contract, table::SitOutScoring};
+use crate::gamemodes::{Gamemodes, Score, TOTAL_TRICKS};
+
+#[derive(Debug, Error)]
+pub enum RuleSetError {
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Unknown rule set format: {0}")]
+    UnknownFormat(String),
+    #[error("The rule set has no contract")]
+    NoContracts,
+    #[error("Contract {idx}: {reason}")]
+    InvalidContract { idx: usize, reason: &'static str },
+}
+
+/// House rules: a named list of contracts.
+///
+/// Contracts are ranked during an auction by their `bid_rank`, or by their
+/// position in the list when they have none. A rule set is validated when it is
+/// created or parsed, so that every contract can be scored.
+///
+/// A TOML rule set looks like:
+///
+/// ```toml
+/// name = "Club rules"
+/// sit_out = "Nothing"
+///
+/// [[contracts]]
+/// contractors_kind = "Team"
+/// max_bid = 13
+/// bid_rank = 0
+/// gamemode = { Emballage = { tricks_to_win = 8, min_points = 2, points_per_suppl_trick = 1 } }
+///
+/// [[contracts]]
+/// contractors_kind = "Other"
+/// bid_rank = 1
+/// gamemode = { Misere = { min_points = 12 } }
+/// multipliers = { win = 1, lose = -1, capot = 2 }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleSet {
+    name: String,
+    contracts: Vec<Contract>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    sit_out: SitOutScoring,
+}
+
+impl RuleSet {
+    /// Creates a rule set from its contracts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rule set has no contract or if a contract is not
+    /// valid.
+    pub fn new(name: impl Into<String>, contracts: Vec<Contract>) -> Result<Self, RuleSetError> {
+        let rule_set = Self {
+            name: name.into(),
+            contracts,
+            sit_out: SitOutScoring::default(),
+        };
+        rule_set.validate()?;
+        Ok(rule_set)
+    }
+
+    #[must_use]
+    pub const fn with_sit_out(mut self, sit_out: SitOutScoring) -> Self {
+        self.sit_out = sit_out;
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn contracts(&self) -> &[Contract] {
+        &self.contracts
+    }
+
+    #[must_use]
+    pub const fn sit_out(&self) -> SitOutScoring {
+        self.sit_out
+    }
+
+    /// Checks that every contract of the rule set can be scored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rule set has no contract, or if a contract has
+    /// tricks or a maximum bid outside of the hand, non-positive points,
+    /// multipliers with the wrong sign, or contractors that cannot play its
+    /// gamemode.
+    pub fn validate(&self) -> Result<(), RuleSetError> {
+        if self.contracts.is_empty() {
+            return Err(RuleSetError::NoContracts);
+        }
+        for (idx, contract) in self.contracts.iter().enumerate() {
+            validate_contract(contract)
+                .map_err(|reason| RuleSetError::InvalidContract { idx, reason })?;
+        }
+        Ok(())
+    }
+
+    /// Parses and validates a rule set written in TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is not valid TOML or does not describe a
+    /// valid rule set.
+    #[cfg(feature = "serde")]
+    pub fn from_toml(toml: &str) -> Result<Self, RuleSetError> {
+        let rule_set: Self = toml::from_str(toml)?;
+        rule_set.validate()?;
+        Ok(rule_set)
+    }
+
+    /// Parses and validates a rule set written in JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is not valid JSON or does not describe a
+    /// valid rule set.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, RuleSetError> {
+        let rule_set: Self = serde_json::from_str(json)?;
+        rule_set.validate()?;
+        Ok(rule_set)
+    }
+
+    /// Loads a rule set from a `.toml` or `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, if its extension is neither
+    /// `toml` nor `json`, or if it does not contain a valid rule set.
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RuleSetError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        match extension.as_str() {
+            "toml" => Self::from_toml(&fs::read_to_string(path)?),
+            "json" => Self::from_json(&fs::read_to_string(path)?),
+            _ => Err(RuleSetError::UnknownFormat(extension)),
+        }
+    }
+}
+
+fn validate_contract(contract: &Contract) -> Result<(), &'static str> {
+    let min_tricks = contract.min_tricks();
+    if !(0..=TOTAL_TRICKS).contains(&min_tricks) {
+        return Err("The tricks to win must be between 0 and 13");
+    }
+    if let Some(max_bid) = contract.max_bid
+        && !(min_tricks..=TOTAL_TRICKS).contains(&max_bid)
+    {
+        return Err("The maximum bid must be between the tricks to win and 13");
+    }
+    let saturated = |t| matches!(contract.gamemode.calculate_score(t).0, i16::MIN | i16::MAX);
+    if (0..=TOTAL_TRICKS).any(saturated) {
+        return Err("The points are out of range");
+    }
+    let (points, _) = contract.gamemode.calculate_score(min_tricks);
+    if points <= 0 || (0..=TOTAL_TRICKS).any(|t| contract.gamemode.calculate_score(t).0 < 0) {
+        return Err("The points must be positive");
+    }
+    let multipliers = contract.multipliers;
+    if multipliers.win <= 0 || multipliers.lose >= 0 || multipliers.capot <= 0 {
+        return Err("Win and capot multipliers must be positive, lose multipliers negative");
+    }
+    if (0..=TOTAL_TRICKS).any(|t| contract.get_score(t).is_err()) {
+        return Err("The multiplied points are out of range");
+    }
+    let kind_allowed = match contract.gamemode {
+        Gamemodes::Emballage(_) | Gamemodes::Troel(_) => {
+            contract.contractors_kind == ContractorsKind::Team
+        }
+        Gamemodes::Seul(_)
+        | Gamemodes::Abondance(_)
+        | Gamemodes::Picolo(_)
+        | Gamemodes::SoloSlim(_) => contract.contractors_kind == ContractorsKind::Solo,
+        Gamemodes::Misere(_)
+        | Gamemodes::GrandeMisere(_)
+        | Gamemodes::GrandeMisereSurTrou(_)
+        | Gamemodes::OpenMisere(_) => contract.contractors_kind != ContractorsKind::Team,
+        Gamemodes::Custom(_) => true,
+    };
+    if !kind_allowed {
+        return Err("These contractors cannot play this gamemode");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::rules::{GameRules, select_rules};
+    use crate::gamemodes::Misere;
+
+    use super::*;
+
+    #[test]
+    fn builtin_rules_are_valid() {
+        for rules in GameRules::builtin() {
+            RuleSet::new(rules.to_string(), select_rules(&rules)).unwrap();
+        }
+    }
+
+    #[test]
+    fn invalid_contracts() {
+        let err = RuleSet::new("empty", Vec::new()).unwrap_err();
+        assert!(matches!(err, RuleSetError::NoContracts));
+
+        let mut contracts = select_rules(&GameRules::French);
+        contracts[1].max_bid = Some(14);
+        let err = RuleSet::new("bid", contracts).unwrap_err();
+        assert!(matches!(err, RuleSetError::InvalidContract { idx: 1, .. }));
+
+        let mut contracts = select_rules(&GameRules::French);
+        contracts[0].contractors_kind = ContractorsKind::Solo;
+        let err = RuleSet::new("kind", contracts).unwrap_err();
+        assert!(matches!(err, RuleSetError::InvalidContract { idx: 0, .. }));
+
+        let mut contracts = select_rules(&GameRules::French);
+        contracts[2].gamemode = Gamemodes::Misere(Misere::new(-4));
+        contracts[2].contractors_kind = ContractorsKind::Other;
+        let err = RuleSet::new("points", contracts).unwrap_err();
+        assert!(matches!(err, RuleSetError::InvalidContract { idx: 2, .. }));
+
+        let mut contracts = select_rules(&GameRules::French);
+        contracts[0].multipliers.lose = 2;
+        let err = RuleSet::new("multipliers", contracts).unwrap_err();
+        assert!(matches!(err, RuleSetError::InvalidContract { idx: 0, .. }));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_toml() {
+        let toml = r#"
+            name = "Club rules"
+            sit_out = "Defender"
+
+            [[contracts]]
+            contractors_kind = "Team"
+            max_bid = 13
+            gamemode = { Emballage = { tricks_to_win = 8, min_points = 2, points_per_suppl_trick = 1 } }
+
+            [[contracts]]
+            contractors_kind = "Other"
+            gamemode = { Misere = { min_points = 12 } }
+            multipliers = { win = 1, lose = -1, capot = 2 }
+        "#;
+        let rule_set = RuleSet::from_toml(toml).unwrap();
+        assert_eq!(rule_set.name(), "Club rules");
+        assert_eq!(rule_set.sit_out(), SitOutScoring::Defender);
+        assert_eq!(rule_set.contracts().len(), 2);
+        assert_eq!(rule_set.contracts()[1].get_score(1).unwrap(), -12);
+
+        let invalid = toml.replace("max_bid = 13", "max_bid = 20");
+        let err = RuleSet::from_toml(&invalid).unwrap_err();
+        assert!(matches!(err, RuleSetError::InvalidContract { idx: 0, .. }));
+        let overflowing = r#"
+            name = "Overflow"
+
+            [[contracts]]
+            contractors_kind = "Solo"
+            gamemode = { Seul = { tricks_to_win = 6, min_points = 6, points_per_suppl_trick = 5000, max_tricks_allowed = 13 } }
+        "#;
+        let err = RuleSet::from_toml(overflowing).unwrap_err();
+        assert!(matches!(err, RuleSetError::InvalidContract { idx: 0, .. }));
+        let multiplied = overflowing.replace("5000", "3").replace(
+            "max_tricks_allowed = 13 } }",
+            "max_tricks_allowed = 13 } }\n            multipliers = { win = 3000, lose = -2, capot = 2 }",
+        );
+        let err = RuleSet::from_toml(&multiplied).unwrap_err();
+        assert!(matches!(err, RuleSetError::InvalidContract { idx: 0, .. }));
+        let err = RuleSet::from_toml("name = ").unwrap_err();
+        assert!(matches!(err, RuleSetError::Toml(_)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_json_file() {
+        let rule_set = RuleSet::new("French", select_rules(&GameRules::French)).unwrap();
+        let json = serde_json::to_string(&rule_set).unwrap();
+        assert_eq!(RuleSet::from_json(&json).unwrap(), rule_set);
+
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("whist-rules-{}.json", std::process::id()));
+        fs::write(&path, &json).unwrap();
+        let loaded = RuleSet::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), rule_set);
+
+        let err = RuleSet::load(dir.join("rules.yaml")).unwrap_err();
+        assert!(matches!(err, RuleSetError::UnknownFormat(ext) if ext == "yaml"));
+    }
+}
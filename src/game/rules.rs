@@ -1,19 +1,35 @@
+use std::fmt;
+
 use crate::{
     game::{
         contractors::{ContractorsKind, ContractorsScore},
         hand::InputError,
+        players::{PlayerId, PlayerIdAndScore},
+        rule_set::RuleSet,
+        table::{Seating, SitOutScoring},
+    },
+    gamemodes::{
+        Abondance, Emballage, Gamemodes, Misere, Picolo, ResultMultipliers, Score, Seul, SoloSlim,
+        TOTAL_TRICKS, Troel,
     },
-    gamemodes::{Emballage, Gamemodes, Misere, Picolo, Score, Seul, TOTAL_TRICKS},
 };
 
 use strum_macros::{Display, EnumIter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Contract {
     pub max_bid: Option<i16>,
     pub contractors_kind: ContractorsKind,
     pub gamemode: Gamemodes,
+    /// Factors applied to the gamemode points, depending on the result.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub multipliers: ResultMultipliers,
+    /// Rank of the contract during an auction, where each trick bid above the
+    /// minimum moves a bid one rank up. Contracts without a rank are ranked by
+    /// their position in the rule set.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bid_rank: Option<u16>,
 }
 
 impl Contract {
@@ -21,159 +37,399 @@ impl Contract {
     pub fn min_tricks(&self) -> i16 {
         self.gamemode.min_tricks()
     }
+
+    /// Returns `true` for a contract imposed by the deal, such as Troel, which
+    /// cannot be bid during an auction.
+    #[must_use]
+    pub const fn is_forced(&self) -> bool {
+        matches!(self.gamemode, Gamemodes::Troel(_))
+    }
+
+    /// Returns the tricks used to score `tricks` taken with `bid`.
+    ///
+    /// The tricks are capped by the maximum bid and lowered by the number of
+    /// tricks bid above the minimum, then capped by the gamemode itself.
+    #[must_use]
+    pub fn counted_tricks(&self, bid: Option<i16>, tricks: i16) -> i16 {
+        let tricks = self.max_bid.map_or(tricks, |max| tricks.clamp(0, max));
+        let tricks = bid.map_or(tricks, |bid| tricks - (bid - self.min_tricks()));
+        self.gamemode.counted_tricks(tricks)
+    }
+
+    /// Returns the score of the contractors for the given tricks, with the
+    /// multipliers of this contract.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the multiplied score is too large.
+    pub fn get_score(&self, tricks: i16) -> Result<i16, InputError> {
+        let (points, result) = self.gamemode.calculate_score(tricks);
+        self.multipliers
+            .apply(points, result)
+            .ok_or(InputError::ScoreOverflow)
+    }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, EnumIter, Display)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameRules {
     Dutch,
     French,
+    /// House rules, usually loaded from a file.
+    Custom(RuleSet),
+}
+
+impl GameRules {
+    /// The built-in rule sets, in place of the former `GameRules::iter()`
+    /// which cannot list [`GameRules::Custom`].
+    #[must_use]
+    pub const fn builtin() -> [Self; 2] {
+        [Self::Dutch, Self::French]
+    }
+
+    /// Name of the rule set, which is the name of the house rules for
+    /// [`GameRules::Custom`].
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Dutch => "Dutch",
+            Self::French => "French",
+            Self::Custom(rule_set) => rule_set.name(),
+        }
+    }
+}
+
+impl fmt::Display for GameRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How the stakes grow after deals where every player passed.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, EnumIter, Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StakePolicy {
+    /// The cards are dealt again and the stakes do not change.
+    #[default]
+    RedealOnly,
+    /// The hand played after one or more passed deals counts double.
+    DoubleNext,
+    /// The stakes double with every passed deal.
+    Cumulative,
+}
+
+impl StakePolicy {
+    /// Returns the multiplier of a hand played after `passed` consecutive passed
+    /// deals.
+    #[must_use]
+    pub const fn multiplier(self, passed: u32) -> i16 {
+        match self {
+            Self::RedealOnly => 1,
+            Self::DoubleNext if passed == 0 => 1,
+            Self::DoubleNext => 2,
+            Self::Cumulative => 2_i16.saturating_pow(passed),
+        }
+    }
+}
+
+/// How the score of the contractors is settled with the defenders.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, EnumIter, Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Settlement {
+    /// The defenders share the opposite of the contractors score. Scores that
+    /// cannot be split evenly between the defenders are rejected.
+    #[default]
+    Split,
+    /// Each defender pays the score of each contractor, so that every score can
+    /// be settled exactly: a 5-point Seul at a table of four wins 15 points and
+    /// costs 5 points to each defender.
+    PerOpponent,
 }
 
+/// Returns the contracts of a rule set.
+///
+/// Contracts added to a built-in rule set are appended to its list, so that
+/// the index of an existing contract never changes. Bids are compared during an
+/// auction with the [`Contract::bid_rank`] of each contract, not with its
+/// position in the list.
 #[must_use]
 pub fn select_rules(rules: &GameRules) -> Vec<Contract> {
     match rules {
-        GameRules::Dutch => {
-            let tricks_to_win = 8;
-            let rules = Emballage::new(tricks_to_win, 2, 1);
-            let emballage = Contract {
-                max_bid: Some(TOTAL_TRICKS),
-                gamemode: Gamemodes::Emballage(rules),
-                contractors_kind: ContractorsKind::Team,
-            };
-            let max_tricks_allowed = 8;
-            let rules = Seul::new(6, 6, 3, max_tricks_allowed);
-
-            let seul = Contract {
-                max_bid: Some(max_tricks_allowed),
-                gamemode: Gamemodes::Seul(rules),
-                contractors_kind: ContractorsKind::Solo,
-            };
-
-            let rules = Misere::new(12);
-
-            let petite_misere = Contract {
-                max_bid: None,
-                contractors_kind: ContractorsKind::Other,
-                gamemode: Gamemodes::Misere(rules),
-            };
-
-            let rules = Misere::new(24);
-
-            let grande_misere = Contract {
-                max_bid: None,
-                contractors_kind: ContractorsKind::Other,
-                gamemode: Gamemodes::GrandeMisere(rules),
-            };
-
-            let rules = Misere::new(36);
-
-            let grande_misere_sur_trou = Contract {
-                max_bid: None,
-                contractors_kind: ContractorsKind::Other,
-                gamemode: Gamemodes::GrandeMisereSurTrou(rules),
-            };
-
-            vec![
-                emballage,
-                seul,
-                petite_misere,
-                grande_misere,
-                grande_misere_sur_trou,
-            ]
-        }
-        GameRules::French => {
-            let tricks_to_win = 8;
-            let rules = Emballage::new(tricks_to_win, 2, 1);
-            let emballage = Contract {
-                max_bid: Some(TOTAL_TRICKS),
-                gamemode: Gamemodes::Emballage(rules),
-                contractors_kind: ContractorsKind::Team,
-            };
-            let max_tricks_allowed = 8;
-            let rules = Seul::new(6, 6, 3, max_tricks_allowed);
-
-            let seul = Contract {
-                max_bid: Some(max_tricks_allowed),
-                gamemode: Gamemodes::Seul(rules),
-                contractors_kind: ContractorsKind::Solo,
-            };
-
-            let rules = Picolo::new(12);
-
-            let picolo = Contract {
-                max_bid: None,
-                gamemode: Gamemodes::Picolo(rules),
-                contractors_kind: ContractorsKind::Solo,
-            };
-
-            vec![emballage, seul, picolo]
-        }
+        GameRules::Dutch => dutch_contracts(),
+        GameRules::French => french_contracts(),
+        GameRules::Custom(rule_set) => rule_set.contracts().to_vec(),
     }
 }
 
+fn dutch_contracts() -> Vec<Contract> {
+    let tricks_to_win = 8;
+    let rules = Emballage::new(tricks_to_win, 2, 1);
+    let emballage = Contract {
+        max_bid: Some(TOTAL_TRICKS),
+        gamemode: Gamemodes::Emballage(rules),
+        contractors_kind: ContractorsKind::Team,
+        multipliers: ResultMultipliers::default(),
+        bid_rank: Some(0),
+    };
+    let max_tricks_allowed = 8;
+    let rules = Seul::new(6, 6, 3, max_tricks_allowed);
+
+    let seul = Contract {
+        max_bid: Some(max_tricks_allowed),
+        gamemode: Gamemodes::Seul(rules),
+        contractors_kind: ContractorsKind::Solo,
+        multipliers: ResultMultipliers::default(),
+        bid_rank: Some(1),
+    };
+
+    let troel = Contract {
+        max_bid: None,
+        contractors_kind: ContractorsKind::Team,
+        multipliers: ResultMultipliers::default(),
+        gamemode: Gamemodes::Troel(Troel::new(8, 4, 2)),
+        bid_rank: None,
+    };
+
+    let abondance = |tricks_to_win, min_points, bid_rank| Contract {
+        max_bid: None,
+        contractors_kind: ContractorsKind::Solo,
+        multipliers: ResultMultipliers::default(),
+        gamemode: Gamemodes::Abondance(Abondance::new(tricks_to_win, min_points, 3)),
+        bid_rank: Some(bid_rank),
+    };
+
+    let rules = Misere::new(12);
+
+    let petite_misere = Contract {
+        max_bid: None,
+        contractors_kind: ContractorsKind::Other,
+        multipliers: ResultMultipliers::default(),
+        gamemode: Gamemodes::Misere(rules),
+        bid_rank: Some(3),
+    };
+
+    let rules = Misere::new(24);
+
+    let grande_misere = Contract {
+        max_bid: None,
+        contractors_kind: ContractorsKind::Other,
+        multipliers: ResultMultipliers::default(),
+        gamemode: Gamemodes::GrandeMisere(rules),
+        bid_rank: Some(6),
+    };
+
+    let rules = Misere::new(36);
+
+    let grande_misere_sur_trou = Contract {
+        max_bid: None,
+        contractors_kind: ContractorsKind::Other,
+        multipliers: ResultMultipliers::default(),
+        gamemode: Gamemodes::GrandeMisereSurTrou(rules),
+        bid_rank: Some(8),
+    };
+
+    let rules = Misere::new(48);
+
+    let open_misere = Contract {
+        max_bid: None,
+        contractors_kind: ContractorsKind::Other,
+        multipliers: ResultMultipliers::default(),
+        gamemode: Gamemodes::OpenMisere(rules),
+        bid_rank: Some(9),
+    };
+
+    let solo_slim = Contract {
+        max_bid: None,
+        contractors_kind: ContractorsKind::Solo,
+        multipliers: ResultMultipliers::default(),
+        gamemode: Gamemodes::SoloSlim(SoloSlim::new(60)),
+        bid_rank: Some(10),
+    };
+
+    vec![
+        emballage,
+        seul,
+        petite_misere,
+        grande_misere,
+        grande_misere_sur_trou,
+        abondance(9, 15, 2),
+        abondance(10, 18, 4),
+        abondance(11, 21, 5),
+        abondance(12, 24, 7),
+        solo_slim,
+        open_misere,
+        troel,
+    ]
+}
+
+fn french_contracts() -> Vec<Contract> {
+    let tricks_to_win = 8;
+    let rules = Emballage::new(tricks_to_win, 2, 1);
+    let emballage = Contract {
+        max_bid: Some(TOTAL_TRICKS),
+        gamemode: Gamemodes::Emballage(rules),
+        contractors_kind: ContractorsKind::Team,
+        multipliers: ResultMultipliers::default(),
+        bid_rank: Some(0),
+    };
+    let max_tricks_allowed = 8;
+    let rules = Seul::new(6, 6, 3, max_tricks_allowed);
+
+    let seul = Contract {
+        max_bid: Some(max_tricks_allowed),
+        gamemode: Gamemodes::Seul(rules),
+        contractors_kind: ContractorsKind::Solo,
+        multipliers: ResultMultipliers::default(),
+        bid_rank: Some(1),
+    };
+
+    let rules = Picolo::new(12);
+
+    let picolo = Contract {
+        max_bid: None,
+        gamemode: Gamemodes::Picolo(rules),
+        contractors_kind: ContractorsKind::Solo,
+        multipliers: ResultMultipliers::default(),
+        bid_rank: Some(2),
+    };
+
+    vec![emballage, seul, picolo]
+}
+
+/// Splits the contractors score between the four players.
+///
+/// Contractors receive their own score while the remaining players share the
+/// opposite amount, so that the sum of all scores is zero.
+///
+/// # Errors
+///
+/// Returns an error if the score cannot be split evenly between the other
+/// players, or if the same player appears twice in a team.
+#[allow(clippy::missing_panics_doc)]
 pub fn calculate_players_score(contractors: &ContractorsScore) -> Result<[i16; 4], InputError> {
-    let mut scores = [0; 4];
-    match contractors {
-        ContractorsScore::Solo(pias) => {
-            let (idx, &score) = pias.as_components();
-            if score % 3 != 0 {
-                return Err(InputError::WrongScore);
-            }
-            for i in 0..4 {
-                if i == *idx {
-                    scores[i] = score;
-                } else {
-                    scores[i] = -score / 3;
-                }
-            }
-            Ok(scores)
-        }
-        ContractorsScore::Team(pias1, pias2) => {
-            let (idx_1, &score_1) = pias1.as_components();
-            let (idx_2, &score_2) = pias2.as_components();
-            if idx_1 == idx_2 {
-                return Err(InputError::InvalidInput("Same player in team"));
-            }
-            if (score_1 + score_2) % 2 != 0 {
-                return Err(InputError::WrongScore);
-            }
+    let scores = settle(
+        contractors,
+        &Seating::four(),
+        SitOutScoring::Nothing,
+        Settlement::Split,
+    )?;
+    Ok(scores.try_into().expect("A table of four"))
+}
 
-            let other_players_score = -(score_1 + score_2) / 2;
-            for i in 0..4 {
-                if i == *idx_1 {
-                    scores[i] = score_1;
-                } else if i == *idx_2 {
-                    scores[i] = score_2;
-                } else {
-                    scores[i] = other_players_score;
-                }
-            }
-            Ok(scores)
+/// Splits the contractors score between the players of a table.
+///
+/// Contractors receive their own score while the defenders share the opposite
+/// amount, so that the sum of all scores is zero. Depending on `sit_out`, the
+/// players sitting out the hand either score nothing or are settled like the
+/// defenders. With [`Settlement::PerOpponent`], each defender pays the score of
+/// each contractor instead, and the contractors are paid by every defender.
+///
+/// # Errors
+///
+/// Returns an error if the score cannot be split evenly between the defenders
+/// with [`Settlement::Split`], if a settled score is too large, if the same
+/// player appears twice among the contractors, or if a contractor is not taking
+/// part in the hand.
+pub fn settle(
+    contractors: &ContractorsScore,
+    seating: &Seating,
+    sit_out: SitOutScoring,
+    settlement: Settlement,
+) -> Result<Vec<i16>, InputError> {
+    let contractors: Vec<&PlayerIdAndScore> = match contractors {
+        ContractorsScore::Solo(pias) => vec![pias],
+        ContractorsScore::Team(pias1, pias2) => vec![pias1, pias2],
+        ContractorsScore::Other(pias) => pias.iter().collect(),
+    };
+    if has_duplicates(&contractors) {
+        return Err(InputError::InvalidInput("Same player among contractors"));
+    }
+    if contractors
+        .iter()
+        .any(|c| c.id.idx() >= seating.players() || seating.is_sitting_out(&c.id))
+    {
+        return Err(InputError::InvalidInput("Contractor not playing this hand"));
+    }
+
+    let defenders: Vec<PlayerId> = (0..seating.players())
+        .map(PlayerId::new)
+        .filter(|p| !contractors.iter().any(|c| c.id == *p))
+        .filter(|p| sit_out == SitOutScoring::Defender || !seating.is_sitting_out(p))
+        .collect();
+    let total = contractors
+        .iter()
+        .try_fold(0_i16, |total, c| total.checked_add(c.score))
+        .ok_or(InputError::ScoreOverflow)?;
+    let Ok(count) = i16::try_from(defenders.len()) else {
+        return Err(InputError::WrongScore);
+    };
+    if contractors.is_empty() || count == 0 {
+        return Err(InputError::WrongScore);
+    }
+    let (per_contractor, per_defender) = match settlement {
+        Settlement::Split if total % count != 0 => return Err(InputError::WrongScore),
+        Settlement::Split => (
+            1,
+            (total / count)
+                .checked_neg()
+                .ok_or(InputError::ScoreOverflow)?,
+        ),
+        Settlement::PerOpponent => (count, total.checked_neg().ok_or(InputError::ScoreOverflow)?),
+    };
+
+    let mut scores = vec![0; seating.players()];
+    for contractor in contractors {
+        scores[contractor.id.idx()] = contractor
+            .score
+            .checked_mul(per_contractor)
+            .ok_or(InputError::ScoreOverflow)?;
+    }
+    for defender in defenders {
+        scores[defender.idx()] = per_defender;
+    }
+    Ok(scores)
+}
+
+/// Settles each declarer independently against the other players, and adds up
+/// the settlements.
+///
+/// This is used when several players declare the same contract, such as misère,
+/// and each of them wins or loses on their own.
+///
+/// # Errors
+///
+/// Returns an error if a declarer score cannot be split between the other
+/// players or is too large, or if the same player appears twice among the
+/// declarers.
+pub fn settle_declarers(
+    declarers: &[PlayerIdAndScore],
+    seating: &Seating,
+    sit_out: SitOutScoring,
+    settlement: Settlement,
+) -> Result<Vec<i16>, InputError> {
+    if has_duplicates(&declarers.iter().collect::<Vec<_>>()) {
+        return Err(InputError::InvalidInput("Same player among declarers"));
+    }
+    let mut scores = vec![0_i16; seating.players()];
+    for declarer in declarers {
+        let declarer_scores = settle(
+            &ContractorsScore::Solo(declarer.clone()),
+            seating,
+            sit_out,
+            settlement,
+        )?;
+        for (score, s) in scores.iter_mut().zip(declarer_scores) {
+            *score = score.checked_add(s).ok_or(InputError::ScoreOverflow)?;
         }
-        ContractorsScore::Other(contractors) => match contractors.len() {
-            1 => calculate_players_score(&ContractorsScore::Solo(
-                contractors.first().expect("Only one element").clone(),
-            )),
-            2 => calculate_players_score(&ContractorsScore::Team(
-                contractors.first().expect("Two elements").clone(),
-                contractors.get(1).expect("Two elements").clone(),
-            )),
-            3 => {
-                let mut last_player_idx = 6;
-                let mut last_player_score = 0;
-                for pias in contractors {
-                    let (idx, &score) = pias.as_components();
-                    last_player_idx -= idx;
-                    last_player_score -= score;
-                    scores[*idx] = score;
-                }
-                scores[last_player_idx] = last_player_score;
-                Ok(scores)
-            }
-            _ => Err(InputError::WrongScore),
-        },
     }
+    Ok(scores)
+}
+
+fn has_duplicates(players: &[&PlayerIdAndScore]) -> bool {
+    players
+        .iter()
+        .enumerate()
+        .any(|(i, p)| players[..i].iter().any(|q| q.id == p.id))
 }
 
 #[cfg(test)]
@@ -190,4 +446,235 @@ mod tests {
 
         assert_eq!(expected_score, emballage_score);
     }
+
+    #[test]
+    fn settle_independent_declarers() {
+        let declarers = [
+            PlayerIdAndScore::new(PlayerId::new(0), 12),
+            PlayerIdAndScore::new(PlayerId::new(1), -24),
+        ];
+        let scores = settle_declarers(
+            &declarers,
+            &Seating::four(),
+            SitOutScoring::Nothing,
+            Settlement::Split,
+        );
+        assert_eq!(scores.unwrap(), [20, -28, 4, 4]);
+
+        let declarers = [PlayerIdAndScore::new(PlayerId::new(0), 5)];
+        let err = settle_declarers(
+            &declarers,
+            &Seating::four(),
+            SitOutScoring::Nothing,
+            Settlement::Split,
+        );
+        assert!(matches!(err.unwrap_err(), InputError::WrongScore));
+    }
+
+    #[test]
+    fn settle_three_players() {
+        let seating = Seating::new(3, PlayerId::new(0));
+        let solo = ContractorsScore::Solo(PlayerIdAndScore::new(PlayerId::new(1), 6));
+        let scores = settle(&solo, &seating, SitOutScoring::Nothing, Settlement::Split).unwrap();
+        assert_eq!(scores, [-3, 6, -3]);
+
+        let team = ContractorsScore::Team(
+            PlayerIdAndScore::new(PlayerId::new(0), 2),
+            PlayerIdAndScore::new(PlayerId::new(2), 2),
+        );
+        let scores = settle(&team, &seating, SitOutScoring::Nothing, Settlement::Split).unwrap();
+        assert_eq!(scores, [2, -4, 2]);
+
+        let odd = ContractorsScore::Solo(PlayerIdAndScore::new(PlayerId::new(1), 9));
+        let err = settle(&odd, &seating, SitOutScoring::Nothing, Settlement::Split).unwrap_err();
+        assert!(matches!(err, InputError::WrongScore));
+    }
+
+    #[test]
+    fn settle_per_opponent() {
+        let seul = ContractorsScore::Solo(PlayerIdAndScore::new(PlayerId::new(2), 5));
+        let err = settle(
+            &seul,
+            &Seating::four(),
+            SitOutScoring::Nothing,
+            Settlement::Split,
+        );
+        assert!(matches!(err.unwrap_err(), InputError::WrongScore));
+        let scores = settle(
+            &seul,
+            &Seating::four(),
+            SitOutScoring::Nothing,
+            Settlement::PerOpponent,
+        );
+        assert_eq!(scores.unwrap(), [-5, -5, 15, -5]);
+
+        let seating = Seating::new(5, PlayerId::new(1));
+        let team = ContractorsScore::Team(
+            PlayerIdAndScore::new(PlayerId::new(0), -3),
+            PlayerIdAndScore::new(PlayerId::new(2), -3),
+        );
+        let scores = settle(
+            &team,
+            &seating,
+            SitOutScoring::Nothing,
+            Settlement::PerOpponent,
+        );
+        assert_eq!(scores.unwrap(), [-6, 0, -6, 6, 6]);
+        let scores = settle(
+            &team,
+            &seating,
+            SitOutScoring::Defender,
+            Settlement::PerOpponent,
+        );
+        assert_eq!(scores.unwrap(), [-9, 6, -9, 6, 6]);
+    }
+
+    #[test]
+    fn settle_sitting_out() {
+        let seating = Seating::new(5, PlayerId::new(1));
+        let solo = ContractorsScore::Solo(PlayerIdAndScore::new(PlayerId::new(0), 6));
+        let scores = settle(&solo, &seating, SitOutScoring::Nothing, Settlement::Split).unwrap();
+        assert_eq!(scores, [6, 0, -2, -2, -2]);
+
+        let team = ContractorsScore::Team(
+            PlayerIdAndScore::new(PlayerId::new(0), 3),
+            PlayerIdAndScore::new(PlayerId::new(2), 3),
+        );
+        let scores = settle(&team, &seating, SitOutScoring::Defender, Settlement::Split).unwrap();
+        assert_eq!(scores, [3, -2, 3, -2, -2]);
+
+        let sitting = ContractorsScore::Solo(PlayerIdAndScore::new(PlayerId::new(1), 6));
+        let err = settle(
+            &sitting,
+            &seating,
+            SitOutScoring::Nothing,
+            Settlement::Split,
+        )
+        .unwrap_err();
+        assert!(matches!(err, InputError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn settle_per_opponent_overflow() {
+        let solo = ContractorsScore::Solo(PlayerIdAndScore::new(PlayerId::new(0), 12_000));
+        let seating = Seating::four();
+        let scores = settle(&solo, &seating, SitOutScoring::Nothing, Settlement::Split);
+        assert_eq!(scores.unwrap(), [12_000, -4_000, -4_000, -4_000]);
+        let err = settle(
+            &solo,
+            &seating,
+            SitOutScoring::Nothing,
+            Settlement::PerOpponent,
+        );
+        assert!(matches!(err, Err(InputError::ScoreOverflow)));
+    }
+
+    #[test]
+    fn settle_duplicate_contractors() {
+        let seating = Seating::four();
+        let twice = PlayerIdAndScore::new(PlayerId::new(0), 12);
+        for contractors in [
+            ContractorsScore::Team(twice.clone(), twice.clone()),
+            ContractorsScore::Other(vec![twice.clone(), twice.clone()]),
+        ] {
+            for settlement in [Settlement::Split, Settlement::PerOpponent] {
+                let err = settle(&contractors, &seating, SitOutScoring::Nothing, settlement);
+                assert!(matches!(err, Err(InputError::InvalidInput(_))));
+            }
+        }
+
+        let declarers = [twice.clone(), twice];
+        let err = settle_declarers(
+            &declarers,
+            &seating,
+            SitOutScoring::Nothing,
+            Settlement::Split,
+        );
+        assert!(matches!(err, Err(InputError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn stake_multiplier() {
+        let multipliers =
+            |policy: StakePolicy| (0..4).map(|n| policy.multiplier(n)).collect::<Vec<_>>();
+        assert_eq!(multipliers(StakePolicy::RedealOnly), [1, 1, 1, 1]);
+        assert_eq!(multipliers(StakePolicy::DoubleNext), [1, 2, 2, 2]);
+        assert_eq!(multipliers(StakePolicy::Cumulative), [1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn contract_multipliers() {
+        let mut misere = select_rules(&GameRules::Dutch).remove(2);
+        assert_eq!(misere.get_score(0).unwrap(), 12);
+        assert_eq!(misere.get_score(1).unwrap(), -24);
+
+        misere.multipliers = ResultMultipliers {
+            lose: -1,
+            ..ResultMultipliers::default()
+        };
+        assert_eq!(misere.get_score(0).unwrap(), 12);
+        assert_eq!(misere.get_score(1).unwrap(), -12);
+
+        let mut emballage = select_rules(&GameRules::Dutch).remove(0);
+        assert_eq!(
+            emballage.get_score(TOTAL_TRICKS).unwrap(),
+            emballage.gamemode.get_score(TOTAL_TRICKS)
+        );
+        emballage.multipliers.capot = 4;
+        assert_eq!(
+            emballage.get_score(TOTAL_TRICKS).unwrap(),
+            2 * emballage.gamemode.get_score(TOTAL_TRICKS)
+        );
+
+        let mut solo_slim = select_rules(&GameRules::Dutch)
+            .into_iter()
+            .find(|c| matches!(c.gamemode, Gamemodes::SoloSlim(_)))
+            .unwrap();
+        solo_slim.multipliers.lose = -1000;
+        assert!(matches!(
+            solo_slim.get_score(0),
+            Err(InputError::ScoreOverflow)
+        ));
+    }
+
+    #[test]
+    fn dutch_baseline_indices() {
+        let names: Vec<String> = select_rules(&GameRules::Dutch)
+            .iter()
+            .take(5)
+            .map(|c| c.gamemode.name())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Emballage",
+                "Seul",
+                "Petite Misere",
+                "Grande Misere",
+                "Grande Misere sur Trou"
+            ]
+        );
+    }
+
+    #[test]
+    fn dutch_abondance() {
+        let contracts = select_rules(&GameRules::Dutch);
+        let abondances: Vec<i16> = contracts
+            .iter()
+            .filter(|c| matches!(c.gamemode, Gamemodes::Abondance(_)))
+            .map(Contract::min_tricks)
+            .collect();
+
+        assert_eq!(abondances, [9, 10, 11, 12]);
+    }
+
+    #[test]
+    fn dutch_solo_slim_is_highest() {
+        let contracts = select_rules(&GameRules::Dutch);
+        let solo_slim = contracts.iter().max_by_key(|c| c.bid_rank).unwrap();
+
+        assert!(matches!(solo_slim.gamemode, Gamemodes::SoloSlim(_)));
+        assert_eq!(solo_slim.contractors_kind, ContractorsKind::Solo);
+        assert_eq!(solo_slim.gamemode.get_score(TOTAL_TRICKS), 60);
+    }
 }
@@ -0,0 +1,561 @@
+use std::{fs, io, path::Path, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::{
+    Game, GameError,
+    contractors::Contractors,
+    hand::{HandBuilder, HistoryEntry},
+    players::{PlayerId, Players},
+    rules::{Contract, GameRules, Settlement, StakePolicy},
+    table::{MAX_PLAYERS, MIN_PLAYERS, Seating},
+};
+
+/// Version of the save format written by [`Game::to_json`].
+pub const FORMAT_VERSION: u64 = 4;
+
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("The save file has no format version")]
+    MissingVersion,
+    #[error("Unsupported save format version: {0}")]
+    UnsupportedVersion(u64),
+    #[error("Invalid save file: {0}")]
+    Invalid(&'static str),
+    #[error(transparent)]
+    GameError(#[from] GameError),
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u64,
+    players: Players,
+    rules: GameRules,
+    contracts: Vec<Contract>,
+    history: Vec<HistoryEntry>,
+    undone: Vec<HistoryEntry>,
+    current_hand: Option<HandDraft>,
+    dealer: PlayerId,
+    stake_policy: StakePolicy,
+    #[serde(default)]
+    settlement: Settlement,
+}
+
+/// The hand being built when the game was saved.
+#[derive(Serialize, Deserialize)]
+struct HandDraft {
+    contract_idx: usize,
+    contractors: Option<Contractors>,
+    bid: Option<i16>,
+    tricks: i16,
+    #[serde(default)]
+    declarer_tricks: Vec<(PlayerId, i16)>,
+    #[serde(default)]
+    independent_declarers: bool,
+}
+
+impl Game {
+    /// Serializes the whole game to a JSON document.
+    ///
+    /// The document contains the players, the rule set and its contracts, the
+    /// hand history (including undone hands) and the hand currently being built.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the game cannot be serialized.
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        let current_hand = self
+            .current_hand
+            .as_ref()
+            .map(|hand| -> Result<HandDraft, SaveError> {
+                Ok(HandDraft {
+                    contract_idx: self
+                        .contract_idx(hand.contract())
+                        .ok_or(GameError::ForeignContract)?,
+                    contractors: hand.contractors().cloned(),
+                    bid: hand.bid(),
+                    tricks: hand.tricks(),
+                    declarer_tricks: hand.declarer_tricks().to_vec(),
+                    independent_declarers: hand.has_independent_declarers(),
+                })
+            })
+            .transpose()?;
+        let file = SaveFile {
+            version: FORMAT_VERSION,
+            players: self.players.clone(),
+            rules: self.rules.clone(),
+            contracts: self.contracts.iter().map(|c| c.as_ref().clone()).collect(),
+            history: self.history.clone(),
+            undone: self.undone.clone(),
+            current_hand,
+            dealer: self.dealer().clone(),
+            stake_policy: self.stake_policy,
+            settlement: self.settlement,
+        };
+        Ok(serde_json::to_string_pretty(&file)?)
+    }
+
+    /// Restores a game from a JSON document written by [`Game::to_json`].
+    ///
+    /// Documents written with an older format version are migrated first. The
+    /// players scores are recomputed from the saved history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is not valid JSON, if its version is not
+    /// supported, or if its content does not describe a valid game.
+    pub fn from_json(json: &str) -> Result<Self, SaveError> {
+        let document = migrate(serde_json::from_str(json)?)?;
+        let file: SaveFile = serde_json::from_value(document)?;
+        file.into_game()
+    }
+
+    /// Saves the game to a JSON file.
+    ///
+    /// The document is first written to a temporary file which then replaces the
+    /// target, so that an interrupted save never corrupts an existing file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the game cannot be serialized or the file cannot be
+    /// written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, self.to_json()?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// Loads a game from a JSON file written by [`Game::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not contain a valid
+    /// game.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SaveError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+impl SaveFile {
+    fn into_game(self) -> Result<Game, SaveError> {
+        let players = self.players.list.len();
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+            return Err(GameError::NotEnoughPlayers.into());
+        }
+        if let GameRules::Custom(rule_set) = &self.rules {
+            rule_set.validate().map_err(GameError::from)?;
+        }
+        let contracts: Vec<Arc<Contract>> = self.contracts.into_iter().map(Arc::new).collect();
+        let recaps = || {
+            self.history
+                .iter()
+                .chain(&self.undone)
+                .filter_map(HistoryEntry::recap)
+        };
+        if recaps().any(|recap| recap.contract_idx >= contracts.len()) {
+            return Err(SaveError::Invalid("Hand with an unknown contract"));
+        }
+        if recaps().any(|recap| recap.scores.len() != players) {
+            return Err(SaveError::Invalid("Hand scores do not match the players"));
+        }
+        if recaps().any(|recap| {
+            let contractors = match &recap.contractors {
+                Contractors::Solo(id) => vec![id],
+                Contractors::Team(id1, id2) => vec![id1, id2],
+                Contractors::Other(declarers) => declarers.iter().map(|d| &d.id).collect(),
+            };
+            contractors
+                .into_iter()
+                .chain(recap.declarer_tricks.iter().map(|(id, _)| id))
+                .chain(&recap.sitting_out)
+                .any(|id| id.idx() >= players)
+        }) {
+            return Err(SaveError::Invalid("Hand with an unknown player"));
+        }
+        if self
+            .history
+            .iter()
+            .chain(&self.undone)
+            .map(HistoryEntry::dealer)
+            .chain([&self.dealer])
+            .any(|dealer| dealer.idx() >= players)
+        {
+            return Err(SaveError::Invalid("Unknown dealer"));
+        }
+
+        let current_hand = self
+            .current_hand
+            .map(|draft| draft.into_builder(&contracts))
+            .transpose()?;
+
+        let mut game = Game {
+            players: self.players,
+            rules: self.rules,
+            contracts,
+            history: self.history,
+            undone: self.undone,
+            current_hand,
+            seating: Seating::new(players, self.dealer),
+            stake_policy: self.stake_policy,
+            settlement: self.settlement,
+        };
+        game.recompute_scores();
+        Ok(game)
+    }
+}
+
+impl HandDraft {
+    fn into_builder(self, contracts: &[Arc<Contract>]) -> Result<HandBuilder, SaveError> {
+        let contract = contracts
+            .get(self.contract_idx)
+            .ok_or(GameError::UnknownContract(self.contract_idx))?;
+        let mut builder = HandBuilder::new(Arc::clone(contract));
+        match self.contractors {
+            Some(Contractors::Other(declarers)) if self.independent_declarers => builder
+                .set_independent_declarers(declarers.into_iter().map(|d| d.id).collect())
+                .map_err(GameError::from)?,
+            Some(contractors) => builder
+                .set_contractors(contractors)
+                .map_err(GameError::from)?,
+            None => {}
+        }
+        if let Some(bid) = self.bid {
+            builder.set_bid(bid).map_err(GameError::from)?;
+        }
+        builder.set_tricks(self.tricks);
+        for (player, tricks) in self.declarer_tricks {
+            builder
+                .set_declarer_tricks(player, tricks)
+                .map_err(GameError::from)?;
+        }
+        Ok(builder)
+    }
+}
+
+/// Upgrades a save document to [`FORMAT_VERSION`].
+///
+/// Older documents are converted one version at a time, so each format change
+/// only has to describe how to go from the previous version to the new one.
+fn migrate(mut document: Value) -> Result<Value, SaveError> {
+    loop {
+        let version = document
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or(SaveError::MissingVersion)?;
+        document = match version {
+            FORMAT_VERSION => return Ok(document),
+            1 => migrate_v1(document)?,
+            2 => migrate_v2(document),
+            3 => migrate_v3(document),
+            v => return Err(SaveError::UnsupportedVersion(v)),
+        };
+    }
+}
+
+/// Version 1 did not track the dealer: the first player dealt the first hand,
+/// and the deal passed to the left after each hand.
+fn migrate_v1(mut document: Value) -> Result<Value, SaveError> {
+    let players = document["players"]["list"]
+        .as_array()
+        .map(Vec::len)
+        .filter(|&n| n > 0)
+        .ok_or(SaveError::Invalid("No players"))?;
+    let played = document["history"].as_array().map_or(0, Vec::len);
+    let undone = document["undone"].as_array().map_or(0, Vec::len);
+
+    if let Some(history) = document["history"].as_array_mut() {
+        for (i, recap) in history.iter_mut().enumerate() {
+            recap["dealer"] = Value::from(i % players);
+        }
+    }
+    // The last undone hand is the first one to be redone.
+    if let Some(undone_hands) = document["undone"].as_array_mut() {
+        for (i, recap) in undone_hands.iter_mut().enumerate() {
+            recap["dealer"] = Value::from((played + undone - 1 - i) % players);
+        }
+    }
+    document["dealer"] = Value::from(played % players);
+    document["version"] = Value::from(2);
+    Ok(document)
+}
+
+/// Version 2 only recorded played hands, and had no stake multiplier.
+fn migrate_v2(mut document: Value) -> Value {
+    for hands in ["history", "undone"] {
+        if let Some(recaps) = document[hands].as_array_mut() {
+            for recap in recaps {
+                recap["multiplier"] = Value::from(1);
+                *recap = serde_json::json!({ "Played": recap.take() });
+            }
+        }
+    }
+    document["stake_policy"] = Value::from("RedealOnly");
+    document["version"] = Value::from(3);
+    document
+}
+
+/// Version 3 stored the whole rule set in every played hand, where version 4
+/// only keeps its name.
+fn migrate_v3(mut document: Value) -> Value {
+    for hands in ["history", "undone"] {
+        if let Some(entries) = document[hands].as_array_mut() {
+            for recap in entries.iter_mut().filter_map(|e| e.get_mut("Played")) {
+                let Some(recap) = recap.as_object_mut() else {
+                    continue;
+                };
+                let name = match recap.remove("rules") {
+                    Some(Value::Object(rules)) => rules
+                        .get("Custom")
+                        .and_then(|rule_set| rule_set.get("name"))
+                        .cloned()
+                        .unwrap_or_default(),
+                    Some(name) => name,
+                    None => Value::Null,
+                };
+                recap.insert("rules_name".to_string(), name);
+            }
+        }
+    }
+    document["version"] = Value::from(4);
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        rule_set::{RuleSet, RuleSetError},
+        rules::select_rules,
+    };
+
+    fn game_in_progress() -> Game {
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+        for (contractor, tricks) in [(0, 8), (1, 3), (2, 6)] {
+            let hand = game.start_hand(1).unwrap();
+            hand.set_contractors(Contractors::Solo(PlayerId(contractor)))
+                .unwrap();
+            hand.set_bid(6).unwrap();
+            hand.set_tricks(tricks);
+            game.commit_hand().unwrap();
+        }
+        game.undo().unwrap();
+
+        let hand = game.start_hand(0).unwrap();
+        hand.set_contractors(Contractors::Team(PlayerId(1), PlayerId(3)))
+            .unwrap();
+        hand.set_bid(9).unwrap();
+        game
+    }
+
+    fn scores(game: &Game) -> Vec<i16> {
+        game.players().list.iter().map(|p| p.score).collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut game = game_in_progress();
+        let json = game.to_json().unwrap();
+        let mut loaded = Game::from_json(&json).unwrap();
+
+        assert_eq!(loaded.players().names(), game.players().names());
+        assert_eq!(scores(&loaded), scores(&game));
+        assert_eq!(loaded.rules(), game.rules());
+        assert_eq!(loaded.contracts().len(), game.contracts().len());
+        assert_eq!(loaded.history().len(), 2);
+        assert_eq!(loaded.dealer(), game.dealer());
+
+        let hand = loaded.current_hand().unwrap();
+        assert_eq!(hand.bid(), Some(9));
+        assert!(matches!(
+            hand.contractors(),
+            Some(Contractors::Team(PlayerId(1), PlayerId(3)))
+        ));
+
+        loaded.redo().unwrap();
+        game.redo().unwrap();
+        assert_eq!(scores(&loaded), scores(&game));
+    }
+
+    #[test]
+    fn scores_are_recomputed() {
+        let game = game_in_progress();
+        let mut document: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        document["players"]["list"][0]["score"] = Value::from(1000);
+
+        let loaded = Game::from_json(&document.to_string()).unwrap();
+        assert_eq!(scores(&loaded), scores(&game));
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let game = game_in_progress();
+        let mut document: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        document["version"] = Value::from(FORMAT_VERSION + 1);
+
+        let err = Game::from_json(&document.to_string()).unwrap_err();
+        assert!(matches!(err, SaveError::UnsupportedVersion(v) if v == FORMAT_VERSION + 1));
+
+        document.as_object_mut().unwrap().remove("version");
+        let err = Game::from_json(&document.to_string()).unwrap_err();
+        assert!(matches!(err, SaveError::MissingVersion));
+    }
+
+    #[test]
+    fn unknown_contract_in_history() {
+        let game = game_in_progress();
+        let mut document: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        document["history"][0]["Played"]["contract_idx"] = Value::from(42);
+
+        let err = Game::from_json(&document.to_string()).unwrap_err();
+        assert!(matches!(err, SaveError::Invalid(_)));
+    }
+
+    #[test]
+    fn inconsistent_hands() {
+        let game = game_in_progress();
+        let document: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+
+        let mut truncated = document.clone();
+        truncated["history"][0]["Played"]["scores"]
+            .as_array_mut()
+            .unwrap()
+            .pop();
+        let err = Game::from_json(&truncated.to_string()).unwrap_err();
+        assert!(matches!(err, SaveError::Invalid(_)));
+
+        let mut unknown = document;
+        unknown["history"][0]["Played"]["contractors"]["Solo"] = Value::from(7);
+        let err = Game::from_json(&unknown.to_string()).unwrap_err();
+        assert!(matches!(err, SaveError::Invalid(_)));
+    }
+
+    #[test]
+    fn invalid_custom_rules() {
+        let rules =
+            GameRules::Custom(RuleSet::new("House", select_rules(&GameRules::Dutch)).unwrap());
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let game = Game::new(players, rules).unwrap();
+        let mut document: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        document["rules"]["Custom"]["contracts"] = Value::Array(Vec::new());
+
+        let err = Game::from_json(&document.to_string()).unwrap_err();
+        assert!(matches!(
+            err,
+            SaveError::GameError(GameError::RuleSetError(RuleSetError::NoContracts))
+        ));
+    }
+
+    #[test]
+    fn save_and_load_file() {
+        let game = game_in_progress();
+        let path = std::env::temp_dir().join(format!("whist-save-{}.json", std::process::id()));
+        game.save(&path).unwrap();
+        let loaded = Game::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(scores(&loaded), scores(&game));
+        assert_eq!(loaded.history().len(), game.history().len());
+    }
+
+    #[test]
+    fn migrate_from_v1() {
+        let game = game_in_progress();
+        let mut document: Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        document["version"] = Value::from(1);
+        let root = document.as_object_mut().unwrap();
+        root.remove("dealer");
+        root.remove("stake_policy");
+        for hands in ["history", "undone"] {
+            for entry in document[hands].as_array_mut().unwrap() {
+                let mut recap = entry["Played"].take();
+                let fields = recap.as_object_mut().unwrap();
+                fields.remove("dealer");
+                fields.remove("multiplier");
+                let rules = fields.remove("rules_name").unwrap();
+                fields.insert("rules".to_string(), rules);
+                *entry = recap;
+            }
+        }
+
+        let mut loaded = Game::from_json(&document.to_string()).unwrap();
+        assert_eq!(scores(&loaded), scores(&game));
+        assert_eq!(loaded.dealer(), &PlayerId(2));
+        let dealers: Vec<&PlayerId> = loaded.history().iter().map(HistoryEntry::dealer).collect();
+        assert_eq!(dealers, [&PlayerId(0), &PlayerId(1)]);
+        assert_eq!(loaded.redo().unwrap().dealer(), &PlayerId(2));
+        assert_eq!(loaded.history()[2].recap().unwrap().multiplier, 1);
+        assert_eq!(loaded.dealer(), &PlayerId(3));
+    }
+
+    #[test]
+    fn migrate_from_v3() {
+        let mut contracts = select_rules(&GameRules::Dutch);
+        contracts[1].multipliers.lose = -1;
+        let rules = GameRules::Custom(RuleSet::new("House", contracts).unwrap());
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, rules).unwrap();
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(0)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        hand.set_tricks(4);
+        game.commit_hand().unwrap();
+        game.redeal(false);
+
+        let json = game.to_json().unwrap();
+        assert_eq!(json.matches("\"House\"").count(), 2);
+        let mut document: Value = serde_json::from_str(&json).unwrap();
+        document["version"] = Value::from(3);
+        let rules = document["rules"].clone();
+        let recap = document["history"][0]["Played"].as_object_mut().unwrap();
+        recap.remove("rules_name");
+        recap.insert("rules".to_string(), rules);
+
+        let loaded = Game::from_json(&document.to_string()).unwrap();
+        assert_eq!(loaded.rules(), game.rules());
+        assert_eq!(loaded.history()[0].recap().unwrap().rules_name, "House");
+        assert!(loaded.history()[1].is_passed());
+        assert_eq!(scores(&loaded), scores(&game));
+    }
+
+    #[test]
+    fn independent_declarers_draft() {
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+        let hand = game.start_hand(2).unwrap();
+        hand.set_independent_declarers(vec![PlayerId(0), PlayerId(2)])
+            .unwrap();
+        hand.set_declarer_tricks(PlayerId(2), 1).unwrap();
+
+        let mut loaded = Game::from_json(&game.to_json().unwrap()).unwrap();
+        let hand = loaded.current_hand().unwrap();
+        assert!(hand.has_independent_declarers());
+        assert_eq!(hand.declarer_tricks(), [(PlayerId(2), 1)]);
+        assert!(loaded.commit_hand().is_err());
+    }
+
+    #[test]
+    fn passed_hands_and_stakes() {
+        let mut game = game_in_progress();
+        game.set_stake_policy(StakePolicy::Cumulative);
+        game.set_settlement(Settlement::PerOpponent);
+        game.redeal(true);
+        game.redeal(false);
+
+        let loaded = Game::from_json(&game.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.stake_policy(), StakePolicy::Cumulative);
+        assert_eq!(loaded.stake_multiplier(), 4);
+        assert_eq!(loaded.settlement(), Settlement::PerOpponent);
+        assert_eq!(loaded.dealer(), game.dealer());
+        assert!(loaded.history()[3].is_passed());
+    }
+}
@@ -0,0 +1,971 @@
+use std::sync::Arc;
+
+use super::{
+    GameError,
+    auction::{Auction, AuctionError, AuctionOutcome},
+    contractors::Contractors,
+    hand::{Hand, HandBuilder, HandRecap, HistoryEntry, PassedHand},
+    players::{PlayerId, Players},
+    rules::{Contract, GameRules, Settlement, StakePolicy, select_rules},
+    table::{MAX_PLAYERS, MIN_PLAYERS, Seating, SitOutScoring},
+};
+
+/// A whole game session.
+///
+/// The session owns the players, the contracts of the chosen rule set and the
+/// history of every committed hand. Players scores are recomputed from the
+/// history whenever it changes, so the running totals always equal the sum of
+/// the recorded hands.
+///
+/// The session also tracks the dealer, who passes the deal to the left after
+/// each committed hand. Deals where every player passed are recorded in the
+/// history too, and may raise the stakes of the next hand depending on the
+/// stake policy.
+///
+/// Contracts are shared through [`Arc`], so a session and the hand being
+/// built can be moved to another thread.
+#[derive(Debug)]
+pub struct Game {
+    pub(super) players: Players,
+    pub(super) rules: GameRules,
+    pub(super) contracts: Vec<Arc<Contract>>,
+    pub(super) history: Vec<HistoryEntry>,
+    pub(super) undone: Vec<HistoryEntry>,
+    pub(super) current_hand: Option<HandBuilder>,
+    pub(super) seating: Seating,
+    pub(super) stake_policy: StakePolicy,
+    pub(super) settlement: Settlement,
+}
+
+impl Game {
+    /// Creates a new game with the given players and rule set.
+    ///
+    /// The first player deals the first hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the game does not contain three to six players, or
+    /// if a custom rule set is not valid.
+    pub fn new(players: Players, rules: GameRules) -> Result<Self, GameError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players.list.len()) {
+            return Err(GameError::NotEnoughPlayers);
+        }
+        if let GameRules::Custom(rule_set) = &rules {
+            rule_set.validate()?;
+        }
+        let contracts = select_rules(&rules).into_iter().map(Arc::new).collect();
+        let seating = Seating::new(players.list.len(), PlayerId::new(0));
+        Ok(Self {
+            seating,
+            players,
+            rules,
+            contracts,
+            history: Vec::new(),
+            undone: Vec::new(),
+            current_hand: None,
+            stake_policy: StakePolicy::default(),
+            settlement: Settlement::default(),
+        })
+    }
+
+    #[must_use]
+    pub const fn players(&self) -> &Players {
+        &self.players
+    }
+
+    #[must_use]
+    pub const fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    #[must_use]
+    pub fn contracts(&self) -> &[Arc<Contract>] {
+        &self.contracts
+    }
+
+    #[must_use]
+    pub fn history(&self) -> &[HistoryEntry] {
+        &self.history
+    }
+
+    #[must_use]
+    pub const fn stake_policy(&self) -> StakePolicy {
+        self.stake_policy
+    }
+
+    pub const fn set_stake_policy(&mut self, policy: StakePolicy) {
+        self.stake_policy = policy;
+    }
+
+    #[must_use]
+    pub const fn settlement(&self) -> Settlement {
+        self.settlement
+    }
+
+    /// Sets how the scores of the next hands are settled with the defenders.
+    ///
+    /// Hands already committed keep their scores.
+    pub const fn set_settlement(&mut self, settlement: Settlement) {
+        self.settlement = settlement;
+    }
+
+    /// Returns the multiplier applied to the scores of the next hand, given the
+    /// passed deals that directly precede it.
+    #[must_use]
+    pub fn stake_multiplier(&self) -> i16 {
+        let passed = self
+            .history
+            .iter()
+            .rev()
+            .take_while(|entry| entry.is_passed())
+            .count();
+        self.stake_policy
+            .multiplier(u32::try_from(passed).unwrap_or(u32::MAX))
+    }
+
+    #[must_use]
+    pub const fn current_hand(&self) -> Option<&HandBuilder> {
+        self.current_hand.as_ref()
+    }
+
+    pub const fn current_hand_mut(&mut self) -> Option<&mut HandBuilder> {
+        self.current_hand.as_mut()
+    }
+
+    /// Starts a new hand with the contract at index `contract_idx`.
+    ///
+    /// Any hand currently being built is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no contract exists at the given index.
+    pub fn start_hand(&mut self, contract_idx: usize) -> Result<&mut HandBuilder, GameError> {
+        let contract = self
+            .contracts
+            .get(contract_idx)
+            .ok_or(GameError::UnknownContract(contract_idx))?;
+        Ok(self
+            .current_hand
+            .insert(HandBuilder::new(Arc::clone(contract))))
+    }
+
+    /// Returns who deals and who takes part in the next hand.
+    #[must_use]
+    pub const fn seating(&self) -> &Seating {
+        &self.seating
+    }
+
+    /// Returns the player who deals the next hand.
+    #[must_use]
+    pub const fn dealer(&self) -> &PlayerId {
+        self.seating.dealer()
+    }
+
+    /// Returns the player who speaks first and leads the first trick of the next
+    /// hand.
+    #[must_use]
+    pub fn leader(&self) -> PlayerId {
+        self.seating.leader()
+    }
+
+    /// Chooses the player who deals the next hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the player is not part of the game.
+    pub fn set_dealer(&mut self, dealer: PlayerId) -> Result<(), GameError> {
+        if dealer.idx() >= self.players.list.len() {
+            return Err(GameError::UnknownPlayer);
+        }
+        self.seating = Seating::new(self.players.list.len(), dealer);
+        Ok(())
+    }
+
+    /// Records a passed deal, where every player passed, and discards the hand
+    /// being built.
+    ///
+    /// The deal passes to the left unless `keep_dealer` is set, in which case the
+    /// same player deals again. Recording a passed deal clears the undone hands.
+    pub fn redeal(&mut self, keep_dealer: bool) {
+        let passed = PassedHand {
+            dealer: self.seating.dealer().clone(),
+            dealer_kept: keep_dealer,
+        };
+        self.current_hand = None;
+        self.undone.clear();
+        self.history.push(HistoryEntry::Passed(passed));
+        if !keep_dealer {
+            self.seating = self.seating.next();
+        }
+    }
+
+    /// Starts an auction among the players taking part in the next hand,
+    /// `first` speaking first. The first player to speak is usually
+    /// [`Game::leader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `first` sits out the next hand or is not part of the
+    /// game.
+    pub fn start_auction(&self, first: PlayerId) -> Result<Auction, GameError> {
+        Ok(Auction::new(
+            &self.contracts,
+            self.seating().active(),
+            first,
+        )?)
+    }
+
+    /// Starts a new hand with the contract, contractors and bid won at
+    /// `auction`.
+    ///
+    /// Declarers of a contract such as misère are set as independent
+    /// declarers, whose tricks must each be set before the hand is committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the auction is not over or if every player passed.
+    pub fn start_auctioned_hand(
+        &mut self,
+        auction: &Auction,
+    ) -> Result<&mut HandBuilder, GameError> {
+        let AuctionOutcome::Contract {
+            contract_idx,
+            contractors,
+            bid,
+        } = auction.outcome()?
+        else {
+            return Err(AuctionError::AllPassed.into());
+        };
+        let hand = self.start_hand(contract_idx)?;
+        if let Contractors::Other(declarers) = contractors {
+            hand.set_independent_declarers(declarers.into_iter().map(|d| d.id).collect())?;
+        } else {
+            hand.set_contractors(contractors)?;
+        }
+        if let Some(bid) = bid {
+            hand.set_bid(bid)?;
+        }
+        Ok(hand)
+    }
+
+    /// Discards the hand currently being built, if any.
+    pub fn cancel_hand(&mut self) {
+        self.current_hand = None;
+    }
+
+    /// Builds and scores the current hand, then records it in the history.
+    ///
+    /// The scores of the hand are multiplied by [`Game::stake_multiplier`]. If
+    /// the hand cannot be built or scored, it is kept so that the faulty input
+    /// can be corrected. Committing a hand clears the undone hands.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no hand is in progress, if the hand is incomplete or
+    /// if its score is too large or cannot be split between the players.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn commit_hand(&mut self) -> Result<&HandRecap, GameError> {
+        let mut hand = self
+            .current_hand
+            .clone()
+            .ok_or(GameError::NoHandInProgress)?
+            .build()?;
+        hand.set_multiplier(self.stake_multiplier());
+        let recap = self.score_hand(hand, &self.seating)?;
+        self.current_hand = None;
+        self.undone.clear();
+        self.history.push(HistoryEntry::Played(recap));
+        self.seating = self.seating.next();
+        self.recompute_scores();
+        Ok(self
+            .history
+            .last()
+            .and_then(HistoryEntry::recap)
+            .expect("A hand has just been recorded"))
+    }
+
+    /// Removes the last entry from the history.
+    ///
+    /// The removed entry can be restored with [`Game::redo`] until a new hand is
+    /// committed. The dealer of the removed entry deals again. Returns the undone
+    /// entry, if any.
+    pub fn undo(&mut self) -> Option<&HistoryEntry> {
+        let entry = self.history.pop()?;
+        self.seating = Seating::new(self.players.list.len(), entry.dealer().clone());
+        self.undone.push(entry);
+        self.recompute_scores();
+        self.undone.last()
+    }
+
+    /// Restores the last undone entry. Returns the restored entry, if any.
+    pub fn redo(&mut self) -> Option<&HistoryEntry> {
+        let entry = self.undone.pop()?;
+        self.seating = self.seating_after(&entry);
+        self.history.push(entry);
+        self.recompute_scores();
+        self.history.last()
+    }
+
+    #[must_use]
+    pub const fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    #[must_use]
+    pub const fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Replaces the committed hand at index `hand_idx` with `hand`.
+    ///
+    /// This allows to fix the contractors, bid or tricks of any past hand. The
+    /// players who sat out the hand and the stake multiplier stay the same. The
+    /// scores of every player are then recomputed from the history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no played hand exists at the given index, if the hand
+    /// contract does not come from this game or if the new hand score cannot be
+    /// split between the players. The history is left untouched on error.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn edit_hand(&mut self, hand_idx: usize, mut hand: Hand) -> Result<&HandRecap, GameError> {
+        let old = self
+            .history
+            .get(hand_idx)
+            .and_then(HistoryEntry::recap)
+            .ok_or(GameError::UnknownHand(hand_idx))?;
+        let seating = Seating::new(self.players.list.len(), old.dealer.clone());
+        hand.set_multiplier(old.multiplier);
+        let recap = self.score_hand(hand, &seating)?;
+        self.history[hand_idx] = HistoryEntry::Played(recap);
+        self.recompute_scores();
+        Ok(self.history[hand_idx]
+            .recap()
+            .expect("A hand has just been recorded"))
+    }
+
+    pub(super) fn recompute_scores(&mut self) {
+        self.players.reset_scores();
+        for recap in self.history.iter().filter_map(HistoryEntry::recap) {
+            self.players.update_score(&recap.scores);
+        }
+    }
+
+    /// Returns the seating of the deal following `entry`.
+    fn seating_after(&self, entry: &HistoryEntry) -> Seating {
+        let seating = Seating::new(self.players.list.len(), entry.dealer().clone());
+        match entry {
+            HistoryEntry::Passed(passed) if passed.dealer_kept => seating,
+            _ => seating.next(),
+        }
+    }
+
+    pub(super) fn contract_idx(&self, contract: &Arc<Contract>) -> Option<usize> {
+        self.contracts.iter().position(|c| Arc::ptr_eq(c, contract))
+    }
+
+    fn score_hand(&self, hand: Hand, seating: &Seating) -> Result<HandRecap, GameError> {
+        let contract_idx = self
+            .contract_idx(hand.contract())
+            .ok_or(GameError::ForeignContract)?;
+        let scores =
+            hand.get_players_score(seating, SitOutScoring::of(&self.rules), self.settlement)?;
+        Ok(hand.as_recap(scores, &self.rules, contract_idx, seating)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::players::PlayerIdAndScore;
+    use crate::game::rule_set::{RuleSet, RuleSetError};
+    use crate::game::{
+        auction::Bid,
+        contractors::{Contractors, ContractorsKind},
+        hand::InputError,
+        hand::{HandBuildError, InputRequest},
+    };
+    use crate::gamemodes::{GameResult, Gamemodes, Seul};
+
+    use super::*;
+
+    fn new_game() -> Game {
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        Game::new(players, GameRules::Dutch).unwrap()
+    }
+
+    fn play_seul(game: &mut Game, contractor: usize, tricks: i16) {
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(contractor)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        hand.set_tricks(tricks);
+        game.commit_hand().unwrap();
+    }
+
+    fn scores(game: &Game) -> Vec<i16> {
+        game.players().list.iter().map(|p| p.score).collect()
+    }
+
+    #[test]
+    fn commit_emballage() {
+        let mut game = new_game();
+        let hand = game.start_hand(0).unwrap();
+        hand.set_contractors(Contractors::Team(PlayerId(0), PlayerId(1)))
+            .unwrap();
+        hand.set_bid(8).unwrap();
+        hand.set_tricks(8);
+
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.scores, [2, 2, -2, -2]);
+        assert_eq!(recap.result, GameResult::Win);
+        assert_eq!(recap.rules_name, "Dutch");
+        assert_eq!(recap.contract_idx, 0);
+        assert_eq!(recap.contractors, ContractorsKind::Team);
+        assert!(game.current_hand().is_none());
+        assert_eq!(game.history().len(), 1);
+
+        assert_eq!(scores(&game), [2, 2, -2, -2]);
+    }
+
+    #[test]
+    fn totals_match_history() {
+        let mut game = new_game();
+        for (contractor, tricks) in [(0, 8), (1, 4), (2, 7)] {
+            play_seul(&mut game, contractor, tricks);
+        }
+
+        for (i, player) in game.players().list.iter().enumerate() {
+            let sum: i16 = game
+                .history()
+                .iter()
+                .filter_map(HistoryEntry::recap)
+                .map(|r| r.scores[i])
+                .sum();
+            assert_eq!(player.score, sum);
+        }
+    }
+
+    #[test]
+    fn incomplete_hand_is_kept() {
+        let mut game = new_game();
+        game.start_hand(0).unwrap();
+
+        let err = game.commit_hand().unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::HandBuildError(HandBuildError::Contractors(_))
+        ));
+        assert!(game.current_hand().is_some());
+        assert!(game.history().is_empty());
+    }
+
+    #[test]
+    fn wrong_score_is_not_recorded() {
+        let mut game = new_game();
+        let hand = game.start_hand(2).unwrap();
+        hand.set_contractors(Contractors::Other(vec![])).unwrap();
+
+        let err = game.commit_hand().unwrap_err();
+        assert!(matches!(err, GameError::InputError(InputError::WrongScore)));
+        assert!(game.history().is_empty());
+        assert!(game.players().list.iter().all(|p| p.score == 0));
+    }
+
+    #[test]
+    fn unknown_contract() {
+        let mut game = new_game();
+        let err = game.start_hand(42).unwrap_err();
+        assert!(matches!(err, GameError::UnknownContract(42)));
+    }
+
+    #[test]
+    fn commit_without_hand() {
+        let mut game = new_game();
+        let err = game.commit_hand().unwrap_err();
+        assert!(matches!(err, GameError::NoHandInProgress));
+    }
+
+    #[test]
+    fn not_enough_players() {
+        let mut players = Players::default();
+        players.add_player("A".to_string()).unwrap();
+        let err = Game::new(players, GameRules::French).unwrap_err();
+        assert!(matches!(err, GameError::NotEnoughPlayers));
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut game = new_game();
+        play_seul(&mut game, 0, 8);
+        play_seul(&mut game, 1, 6);
+        assert_eq!(scores(&game), [10, 2, -6, -6]);
+
+        let undone = game.undo().unwrap().recap().unwrap();
+        assert_eq!(undone.scores, [-2, 6, -2, -2]);
+        assert_eq!(scores(&game), [12, -4, -4, -4]);
+        assert_eq!(game.history().len(), 1);
+
+        game.undo().unwrap();
+        assert!(game.undo().is_none());
+        assert_eq!(scores(&game), [0, 0, 0, 0]);
+
+        game.redo().unwrap();
+        game.redo().unwrap();
+        assert!(game.redo().is_none());
+        assert_eq!(scores(&game), [10, 2, -6, -6]);
+    }
+
+    #[test]
+    fn commit_clears_redo() {
+        let mut game = new_game();
+        play_seul(&mut game, 0, 8);
+        game.undo().unwrap();
+        assert!(game.can_redo());
+
+        play_seul(&mut game, 2, 6);
+        assert!(!game.can_redo());
+        assert_eq!(scores(&game), [-2, -2, 6, -2]);
+    }
+
+    #[test]
+    fn edit_past_hand() {
+        let mut game = new_game();
+        play_seul(&mut game, 0, 3);
+        play_seul(&mut game, 1, 6);
+
+        let mut hand = HandBuilder::new(Arc::clone(&game.contracts()[1]));
+        hand.set_contractors(Contractors::Solo(PlayerId(0)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        hand.set_tricks(8);
+        let recap = game.edit_hand(0, hand.build().unwrap()).unwrap();
+
+        assert_eq!(recap.scores, [12, -4, -4, -4]);
+        assert_eq!(recap.result, GameResult::Win);
+        assert_eq!(game.history().len(), 2);
+        assert_eq!(scores(&game), [10, 2, -6, -6]);
+    }
+
+    #[test]
+    fn edit_unknown_hand() {
+        let mut game = new_game();
+        let mut hand = HandBuilder::new(Arc::clone(&game.contracts()[1]));
+        hand.set_contractors(Contractors::Solo(PlayerId(0)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+
+        let err = game.edit_hand(0, hand.build().unwrap()).unwrap_err();
+        assert!(matches!(err, GameError::UnknownHand(0)));
+    }
+
+    #[test]
+    fn recap_result() {
+        let mut game = new_game();
+        play_seul(&mut game, 3, 2);
+        let recap = game.history()[0].recap().unwrap();
+        assert_eq!(recap.result, GameResult::Lose);
+        assert_eq!(recap.contract_idx, 1);
+        assert!(matches!(recap.contractors, Contractors::Solo(PlayerId(3))));
+    }
+
+    #[test]
+    fn edit_with_foreign_contract() {
+        let mut game = new_game();
+        play_seul(&mut game, 0, 6);
+
+        let other = new_game();
+        let mut hand = HandBuilder::new(Arc::clone(&other.contracts()[1]));
+        hand.set_contractors(Contractors::Solo(PlayerId(0)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+
+        let err = game.edit_hand(0, hand.build().unwrap()).unwrap_err();
+        assert!(matches!(err, GameError::ForeignContract));
+    }
+
+    #[test]
+    fn auctioned_hand() {
+        let mut game = new_game();
+        let mut auction = game.start_auction(PlayerId(1)).unwrap();
+        for (player, bid) in [(1, Bid::Contract(1)), (2, Bid::Pass), (3, Bid::Pass)] {
+            auction.bid(&PlayerId(player), bid).unwrap();
+        }
+        let err = game.start_auctioned_hand(&auction).unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::AuctionError(AuctionError::NotFinished)
+        ));
+
+        auction.bid(&PlayerId(0), Bid::Pass).unwrap();
+        let hand = game.start_auctioned_hand(&auction).unwrap();
+        assert_eq!(hand.bid(), Some(6));
+        hand.set_tricks(6);
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.contract_idx, 1);
+        assert_eq!(recap.scores, [-2, 6, -2, -2]);
+    }
+
+    #[test]
+    fn auctioned_misere() {
+        let mut game = new_game();
+        let mut auction = game.start_auction(PlayerId(0)).unwrap();
+        for (player, bid) in [
+            (0, Bid::Contract(2)),
+            (1, Bid::Join),
+            (2, Bid::Pass),
+            (3, Bid::Pass),
+        ] {
+            auction.bid(&PlayerId(player), bid).unwrap();
+        }
+        let hand = game.start_auctioned_hand(&auction).unwrap();
+        assert!(hand.has_independent_declarers());
+        assert!(matches!(
+            hand.next_request(),
+            InputRequest::DeclarerTricks(PlayerId(0))
+        ));
+        hand.set_declarer_tricks(PlayerId(0), 0).unwrap();
+        assert!(matches!(
+            hand.next_request(),
+            InputRequest::DeclarerTricks(PlayerId(1))
+        ));
+        let err = game.commit_hand().unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::HandBuildError(HandBuildError::Contractors(_))
+        ));
+        assert!(game.history().is_empty());
+
+        let hand = game.current_hand_mut().unwrap();
+        hand.set_declarer_tricks(PlayerId(1), 2).unwrap();
+        assert!(matches!(hand.next_request(), InputRequest::Done));
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.scores, [20, -28, 4, 4]);
+        assert_eq!(recap.result, GameResult::Lose);
+    }
+
+    #[test]
+    fn auction_all_pass() {
+        let mut game = new_game();
+        let mut auction = game.start_auction(PlayerId(0)).unwrap();
+        for player in 0..4 {
+            auction.bid(&PlayerId(player), Bid::Pass).unwrap();
+        }
+        let err = game.start_auctioned_hand(&auction).unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::AuctionError(AuctionError::AllPassed)
+        ));
+        assert!(game.current_hand().is_none());
+    }
+
+    #[test]
+    fn open_misere() {
+        let mut game = new_game();
+        let open_misere = game
+            .contracts()
+            .iter()
+            .position(|c| matches!(c.gamemode, Gamemodes::OpenMisere(_)))
+            .unwrap();
+
+        let hand = game.start_hand(open_misere).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(2)))
+            .unwrap();
+        hand.set_tricks(0);
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.gamemode_name, "Misere op Tafel");
+        assert_eq!(recap.scores, [-16, -16, 48, -16]);
+
+        let hand = game.start_hand(open_misere).unwrap();
+        hand.set_contractors(Contractors::Other(vec![
+            PlayerIdAndScore::new(PlayerId(0), 48),
+            PlayerIdAndScore::new(PlayerId(1), -96),
+        ]))
+        .unwrap();
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.scores, [48, -96, 24, 24]);
+    }
+
+    #[test]
+    fn troel() {
+        let mut game = new_game();
+        let troel = game
+            .contracts()
+            .iter()
+            .position(|c| matches!(c.gamemode, Gamemodes::Troel(_)))
+            .unwrap();
+
+        let hand = game.start_hand(troel).unwrap();
+        assert!(matches!(hand.next_request(), InputRequest::ContractorsTeam));
+        hand.set_contractors(Contractors::Team(PlayerId(3), PlayerId(1)))
+            .unwrap();
+        assert!(matches!(hand.next_request(), InputRequest::Done));
+        hand.set_tricks(9);
+
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.gamemode_name, "Troel");
+        assert_eq!(recap.scores, [-6, 6, -6, 6]);
+    }
+
+    #[test]
+    fn independent_misere_declarers() {
+        let mut game = new_game();
+        let hand = game.start_hand(2).unwrap();
+        hand.set_contractors(Contractors::Other(vec![
+            PlayerIdAndScore::from_id(PlayerId(0)),
+            PlayerIdAndScore::from_id(PlayerId(2)),
+        ]))
+        .unwrap();
+        hand.set_declarer_tricks(PlayerId(0), 0).unwrap();
+        let err = hand.set_declarer_tricks(PlayerId(1), 0).unwrap_err();
+        assert!(matches!(err, HandBuildError::Contractors(_)));
+
+        let err = game.commit_hand().unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::HandBuildError(HandBuildError::Contractors(_))
+        ));
+
+        let hand = game.current_hand_mut().unwrap();
+        hand.set_declarer_tricks(PlayerId(2), 2).unwrap();
+        let recap = game.commit_hand().unwrap();
+
+        // Player 0 wins 12 and player 2 loses 24, each against the three others.
+        assert_eq!(recap.scores, [20, 4, -28, 4]);
+        assert_eq!(recap.scores.iter().sum::<i16>(), 0);
+        assert_eq!(recap.result, GameResult::Lose);
+        let Contractors::Other(declarers) = &recap.contractors else {
+            panic!("Several declarers were expected");
+        };
+        assert_eq!(declarers[0].score, 12);
+        assert_eq!(declarers[1].score, -24);
+    }
+
+    #[test]
+    fn five_players() {
+        let players = Players::from_list(&["A", "B", "C", "D", "E"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+        assert_eq!(game.seating().sitting_out(), [PlayerId(0)]);
+        let err = game.start_auction(PlayerId(0)).unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::AuctionError(AuctionError::UnknownPlayer)
+        ));
+
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(0)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        let err = game.commit_hand().unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::InputError(InputError::InvalidInput(_))
+        ));
+
+        play_seul(&mut game, 1, 6);
+        let recap = game.history()[0].recap().unwrap();
+        assert_eq!(recap.scores, [0, 6, -2, -2, -2]);
+        assert_eq!(recap.sitting_out, [PlayerId(0)]);
+        assert_eq!(game.seating().sitting_out(), [PlayerId(1)]);
+
+        play_seul(&mut game, 0, 6);
+        assert_eq!(scores(&game), [6, 6, -4, -4, -4]);
+    }
+
+    #[test]
+    fn six_players_auction() {
+        let players = Players::from_list(&["A", "B", "C", "D", "E", "F"]).unwrap();
+        let game = Game::new(players, GameRules::French).unwrap();
+        let mut auction = game.start_auction(PlayerId(2)).unwrap();
+        for player in [2, 4, 5, 1] {
+            auction.bid(&PlayerId(player), Bid::Pass).unwrap();
+        }
+        assert!(auction.is_finished());
+    }
+
+    #[test]
+    fn three_players() {
+        let players = Players::from_list(&["A", "B", "C"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+        assert!(game.seating().sitting_out().is_empty());
+
+        let hand = game.start_hand(0).unwrap();
+        hand.set_contractors(Contractors::Team(PlayerId(0), PlayerId(2)))
+            .unwrap();
+        hand.set_bid(8).unwrap();
+        hand.set_tricks(8);
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.scores, [2, -4, 2]);
+    }
+
+    #[test]
+    fn french_sitting_out_defends() {
+        let players = Players::from_list(&["A", "B", "C", "D", "E"]).unwrap();
+        let mut game = Game::new(players, GameRules::French).unwrap();
+        let hand = game.start_hand(0).unwrap();
+        hand.set_contractors(Contractors::Team(PlayerId(1), PlayerId(2)))
+            .unwrap();
+        hand.set_bid(8).unwrap();
+        hand.set_tricks(9);
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.scores, [-2, 3, 3, -2, -2]);
+    }
+
+    #[test]
+    fn dealer_rotation() {
+        let mut game = new_game();
+        assert_eq!(game.dealer(), &PlayerId(0));
+        assert_eq!(game.leader(), PlayerId(1));
+
+        play_seul(&mut game, 0, 6);
+        assert_eq!(game.history()[0].dealer(), &PlayerId(0));
+        assert_eq!(game.dealer(), &PlayerId(1));
+
+        game.redeal(true);
+        assert_eq!(game.dealer(), &PlayerId(1));
+        game.redeal(false);
+        assert_eq!(game.dealer(), &PlayerId(2));
+        play_seul(&mut game, 0, 6);
+        assert_eq!(game.history()[3].dealer(), &PlayerId(2));
+
+        game.undo().unwrap();
+        assert_eq!(game.dealer(), &PlayerId(2));
+        game.undo().unwrap();
+        assert_eq!(game.dealer(), &PlayerId(1));
+        game.redo().unwrap();
+        game.redo().unwrap();
+        assert_eq!(game.dealer(), &PlayerId(3));
+        assert_eq!(game.leader(), PlayerId(0));
+
+        game.set_dealer(PlayerId(1)).unwrap();
+        assert_eq!(game.leader(), PlayerId(2));
+        let err = game.set_dealer(PlayerId(4)).unwrap_err();
+        assert!(matches!(err, GameError::UnknownPlayer));
+    }
+
+    #[test]
+    fn passed_hands_double_stakes() {
+        let mut game = new_game();
+        game.redeal(false);
+        play_seul(&mut game, 0, 6);
+        assert_eq!(game.history()[1].recap().unwrap().multiplier, 1);
+
+        game.set_stake_policy(StakePolicy::DoubleNext);
+        game.redeal(false);
+        game.redeal(false);
+        assert_eq!(game.stake_multiplier(), 2);
+        play_seul(&mut game, 1, 6);
+        let recap = game.history()[4].recap().unwrap();
+        assert_eq!(recap.multiplier, 2);
+        assert_eq!(recap.scores, [-4, 12, -4, -4]);
+        assert_eq!(game.stake_multiplier(), 1);
+
+        game.set_stake_policy(StakePolicy::Cumulative);
+        game.redeal(true);
+        game.redeal(true);
+        assert_eq!(game.stake_multiplier(), 4);
+        play_seul(&mut game, 2, 6);
+        assert_eq!(scores(&game), [-6, 2, 18, -14]);
+
+        // Editing a hand keeps its multiplier, and passed deals cannot be edited.
+        let mut hand = HandBuilder::new(Arc::clone(&game.contracts()[1]));
+        hand.set_contractors(Contractors::Solo(PlayerId(3)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        hand.set_tricks(6);
+        let err = game
+            .edit_hand(2, hand.clone().build().unwrap())
+            .unwrap_err();
+        assert!(matches!(err, GameError::UnknownHand(2)));
+        let recap = game.edit_hand(4, hand.build().unwrap()).unwrap();
+        assert_eq!(recap.scores, [-4, -4, -4, 12]);
+    }
+
+    #[test]
+    fn cumulative_stakes_overflow() {
+        let mut game = new_game();
+        game.set_stake_policy(StakePolicy::Cumulative);
+        for _ in 0..20 {
+            game.redeal(true);
+        }
+        assert_eq!(game.stake_multiplier(), i16::MAX);
+
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(0)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        hand.set_tricks(6);
+        let err = game.commit_hand().unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::InputError(InputError::ScoreOverflow)
+        ));
+        assert!(game.current_hand().is_some());
+        assert_eq!(scores(&game), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn custom_rules() {
+        let mut contracts = select_rules(&GameRules::French);
+        contracts[1].multipliers.lose = -1;
+        let rule_set = RuleSet::new("House", contracts).unwrap();
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, GameRules::Custom(rule_set)).unwrap();
+        assert_eq!(game.contracts().len(), 3);
+
+        play_seul(&mut game, 0, 5);
+        assert_eq!(scores(&game), [-9, 3, 3, 3]);
+
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let err = Game::new(players, GameRules::Custom(RuleSet::default())).unwrap_err();
+        assert!(matches!(
+            err,
+            GameError::RuleSetError(RuleSetError::NoContracts)
+        ));
+    }
+
+    #[test]
+    fn per_opponent_settlement() {
+        let mut contracts = select_rules(&GameRules::French);
+        contracts[1].gamemode = Gamemodes::Seul(Seul::new(6, 5, 3, 8));
+        let rule_set = RuleSet::new("House", contracts).unwrap();
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, GameRules::Custom(rule_set)).unwrap();
+
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(0)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        hand.set_tricks(6);
+        let err = game.commit_hand().unwrap_err();
+        assert!(matches!(err, GameError::InputError(InputError::WrongScore)));
+
+        game.set_settlement(Settlement::PerOpponent);
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.scores, [15, -5, -5, -5]);
+
+        play_seul(&mut game, 1, 5);
+        assert_eq!(scores(&game), [31, -53, 11, 11]);
+    }
+
+    #[test]
+    fn send_between_threads() {
+        const fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Game>();
+        assert_send_sync::<Hand>();
+        assert_send_sync::<HandBuilder>();
+
+        let mut game = new_game();
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(0)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        let game = std::thread::spawn(move || {
+            game.current_hand_mut().unwrap().set_tricks(6);
+            game.commit_hand().unwrap();
+            game
+        })
+        .join()
+        .unwrap();
+        assert_eq!(scores(&game), [6, -2, -2, -2]);
+    }
+}
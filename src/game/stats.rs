@@ -0,0 +1,393 @@
+use std::collections::BTreeMap;
+
+use super::{
+    Game,
+    contractors::Contractors,
+    export,
+    hand::{HandRecap, HistoryEntry},
+    players::{PlayerId, Players},
+};
+use crate::gamemodes::GameResult;
+
+/// Hands declared by a player with one gamemode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GamemodeStats {
+    pub declared: u32,
+    pub won: u32,
+}
+
+impl GamemodeStats {
+    /// Returns the share of declared hands that were won, or `None` if no hand
+    /// was declared.
+    #[must_use]
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.declared > 0).then(|| f64::from(self.won) / f64::from(self.declared))
+    }
+}
+
+/// Statistics of a player over a game history.
+///
+/// A player declares a hand when they are one of its contractors, and defends
+/// it when they take part in the hand without being a contractor. Hands sat out
+/// and passed deals are not counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerStats {
+    pub player: PlayerId,
+    /// Hands declared by the player, by gamemode name.
+    pub gamemodes: BTreeMap<String, GamemodeStats>,
+    pub defended: u32,
+    /// Points won or lost in the hands declared by the player.
+    pub declarer_points: i32,
+    /// Points won or lost in the hands defended by the player.
+    pub defender_points: i32,
+    /// Score of the hand where the player won or lost the most points.
+    pub biggest_swing: i16,
+    /// Hands where the player was a contractor and every trick was won.
+    pub capots: u32,
+}
+
+impl PlayerStats {
+    const fn new(player: PlayerId) -> Self {
+        Self {
+            player,
+            gamemodes: BTreeMap::new(),
+            defended: 0,
+            declarer_points: 0,
+            defender_points: 0,
+            biggest_swing: 0,
+            capots: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn declared(&self) -> u32 {
+        self.gamemodes.values().map(|g| g.declared).sum()
+    }
+
+    /// Returns the average points won or lost per hand declared.
+    #[must_use]
+    pub fn average_as_declarer(&self) -> Option<f64> {
+        average(self.declarer_points, self.declared())
+    }
+
+    /// Returns the average points won or lost per hand defended.
+    #[must_use]
+    pub fn average_as_defender(&self) -> Option<f64> {
+        average(self.defender_points, self.defended)
+    }
+
+    fn record(&mut self, recap: &HandRecap) {
+        let idx = self.player.idx();
+        if recap.sitting_out.contains(&self.player) {
+            return;
+        }
+        let score = recap.scores.get(idx).copied().unwrap_or_default();
+        if score.abs() > self.biggest_swing.abs() {
+            self.biggest_swing = score;
+        }
+
+        let Some(won) = declarer_won(recap, &self.player) else {
+            self.defended += 1;
+            self.defender_points += i32::from(score);
+            return;
+        };
+        let gamemode = self
+            .gamemodes
+            .entry(recap.gamemode_name.clone())
+            .or_default();
+        gamemode.declared += 1;
+        gamemode.won += u32::from(won);
+        self.declarer_points += i32::from(score);
+        self.capots += u32::from(recap.result == GameResult::Capot);
+    }
+}
+
+/// Returns the statistics of each of the `players` over a game history.
+#[must_use]
+pub fn player_stats(players: usize, history: &[HistoryEntry]) -> Vec<PlayerStats> {
+    let mut stats: Vec<PlayerStats> = (0..players)
+        .map(|idx| PlayerStats::new(PlayerId::new(idx)))
+        .collect();
+    for recap in history.iter().filter_map(HistoryEntry::recap) {
+        for player in &mut stats {
+            player.record(recap);
+        }
+    }
+    stats
+}
+
+/// Team hands played together by two players.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PartnershipStats {
+    pub hands: u32,
+    pub won: u32,
+    /// Points won or lost by each of the partners in these hands.
+    pub points: i32,
+}
+
+impl PartnershipStats {
+    /// Returns the share of hands won together, or `None` if the players never
+    /// partnered.
+    #[must_use]
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.hands > 0).then(|| f64::from(self.won) / f64::from(self.hands))
+    }
+}
+
+/// Pairwise statistics of the teams formed during a game.
+///
+/// Only hands declared by a team of two, such as Emballage or Troel, are
+/// counted. The table lists every pair of players who partnered at least once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partnerships {
+    names: Vec<String>,
+    matrix: Vec<Vec<PartnershipStats>>,
+}
+
+impl Partnerships {
+    #[must_use]
+    pub fn new(players: &Players, history: &[HistoryEntry]) -> Self {
+        let names = players.names();
+        let mut matrix = vec![vec![PartnershipStats::default(); names.len()]; names.len()];
+        for recap in history.iter().filter_map(HistoryEntry::recap) {
+            let Contractors::Team(id1, id2) = &recap.contractors else {
+                continue;
+            };
+            let points = i32::from(recap.scores.get(id1.idx()).copied().unwrap_or_default());
+            for (a, b) in [(id1, id2), (id2, id1)] {
+                let Some(stats) = matrix.get_mut(a.idx()).and_then(|row| row.get_mut(b.idx()))
+                else {
+                    continue;
+                };
+                stats.hands += 1;
+                stats.won += u32::from(recap.result != GameResult::Lose);
+                stats.points += points;
+            }
+        }
+        Self { names, matrix }
+    }
+
+    /// Returns the statistics of two players partnering, in either order.
+    #[must_use]
+    pub fn get(&self, player: &PlayerId, partner: &PlayerId) -> Option<&PartnershipStats> {
+        self.matrix.get(player.idx())?.get(partner.idx())
+    }
+
+    #[must_use]
+    pub fn header() -> Vec<String> {
+        ["Partners", "Hands", "Won", "Success", "Points"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Returns one row for each pair of players who partnered at least once.
+    #[must_use]
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        let mut rows = Vec::new();
+        for (a, row) in self.matrix.iter().enumerate() {
+            for (b, stats) in row.iter().enumerate().skip(a + 1) {
+                let Some(rate) = stats.success_rate() else {
+                    continue;
+                };
+                rows.push(vec![
+                    format!("{} & {}", self.names[a], self.names[b]),
+                    stats.hands.to_string(),
+                    stats.won.to_string(),
+                    format!("{:.0}%", rate * 100.0),
+                    stats.points.to_string(),
+                ]);
+            }
+        }
+        rows
+    }
+
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        export::csv(&Self::header(), &self.rows())
+    }
+
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        export::markdown(&Self::header(), &self.rows())
+    }
+}
+
+impl Game {
+    #[must_use]
+    pub fn stats(&self) -> Vec<PlayerStats> {
+        player_stats(self.players.list.len(), &self.history)
+    }
+
+    #[must_use]
+    pub fn partnerships(&self) -> Partnerships {
+        Partnerships::new(&self.players, &self.history)
+    }
+}
+
+/// Returns whether the player won the hand they declared, or `None` if they
+/// did not declare it.
+///
+/// Each declarer of a contract played by several declarers wins or loses on
+/// their own.
+fn declarer_won(recap: &HandRecap, player: &PlayerId) -> Option<bool> {
+    match &recap.contractors {
+        Contractors::Solo(id) => (id == player).then_some(recap.result != GameResult::Lose),
+        Contractors::Team(id1, id2) => {
+            (id1 == player || id2 == player).then_some(recap.result != GameResult::Lose)
+        }
+        Contractors::Other(declarers) => declarers
+            .iter()
+            .find(|d| d.id == *player)
+            .map(|d| d.score > 0),
+    }
+}
+
+fn average(points: i32, hands: u32) -> Option<f64> {
+    (hands > 0).then(|| f64::from(points) / f64::from(hands))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        players::{PlayerIdAndScore, Players},
+        rules::GameRules,
+    };
+
+    use super::*;
+
+    fn played_game() -> Game {
+        let players = Players::from_list(&["Ann", "Bob", "Cid", "Dan", "Eve"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+
+        // Ann deals and sits out.
+        let hand = game.start_hand(0).unwrap();
+        hand.set_contractors(Contractors::Team(PlayerId(1), PlayerId(2)))
+            .unwrap();
+        hand.set_bid(8).unwrap();
+        hand.set_tricks(13);
+        game.commit_hand().unwrap();
+
+        // Bob deals and sits out.
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(2)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        hand.set_tricks(4);
+        game.commit_hand().unwrap();
+
+        game.redeal(false);
+        game
+    }
+
+    #[test]
+    fn declarers_and_defenders() {
+        let game = played_game();
+        let recaps: Vec<&HandRecap> = game
+            .history()
+            .iter()
+            .filter_map(HistoryEntry::recap)
+            .collect();
+        let stats = game.stats();
+        assert_eq!(stats.len(), 5);
+
+        let ann = &stats[0];
+        assert_eq!(ann.declared(), 0);
+        assert_eq!(ann.defended, 1);
+        assert_eq!(ann.defender_points, i32::from(recaps[1].scores[0]));
+        assert_eq!(ann.average_as_declarer(), None);
+
+        let cid = &stats[2];
+        assert_eq!(cid.declared(), 2);
+        assert_eq!(cid.defended, 0);
+        assert_eq!(cid.capots, 1);
+        assert_eq!(
+            cid.gamemodes["Emballage"],
+            GamemodeStats {
+                declared: 1,
+                won: 1
+            }
+        );
+        assert_eq!(cid.gamemodes["Seul"].success_rate(), Some(0.0));
+        assert_eq!(
+            cid.declarer_points,
+            i32::from(recaps[0].scores[2] + recaps[1].scores[2])
+        );
+        assert_eq!(cid.biggest_swing, recaps[1].scores[2]);
+
+        let bob = &stats[1];
+        assert_eq!(bob.declared(), 1);
+        assert_eq!(bob.defended, 0);
+        assert_eq!(bob.capots, 1);
+        assert_eq!(bob.gamemodes["Emballage"].success_rate(), Some(1.0));
+    }
+
+    #[test]
+    fn independent_declarers() {
+        let players = Players::from_list(&["Ann", "Bob", "Cid", "Dan"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+        let hand = game.start_hand(2).unwrap();
+        hand.set_contractors(Contractors::Other(vec![
+            PlayerIdAndScore::from_id(PlayerId(0)),
+            PlayerIdAndScore::from_id(PlayerId(3)),
+        ]))
+        .unwrap();
+        hand.set_declarer_tricks(PlayerId(0), 0).unwrap();
+        hand.set_declarer_tricks(PlayerId(3), 1).unwrap();
+        game.commit_hand().unwrap();
+
+        let stats = game.stats();
+        assert_eq!(stats[0].gamemodes["Petite Misere"].won, 1);
+        assert_eq!(stats[3].gamemodes["Petite Misere"].won, 0);
+        assert_eq!(stats[1].defended, 1);
+        assert_eq!(
+            stats[1].average_as_defender(),
+            Some(f64::from(game.history()[0].recap().unwrap().scores[1]))
+        );
+    }
+
+    #[test]
+    fn partnerships() {
+        let players = Players::from_list(&["Ann", "Bob", "Cid", "Dan"]).unwrap();
+        let mut game = Game::new(players, GameRules::Dutch).unwrap();
+        for (partner, tricks) in [(2, 10), (2, 6), (1, 8)] {
+            let hand = game.start_hand(0).unwrap();
+            hand.set_contractors(Contractors::Team(PlayerId(0), PlayerId(partner)))
+                .unwrap();
+            hand.set_bid(8).unwrap();
+            hand.set_tricks(tricks);
+            game.commit_hand().unwrap();
+        }
+        let hand = game.start_hand(1).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId(3)))
+            .unwrap();
+        hand.set_bid(6).unwrap();
+        hand.set_tricks(6);
+        game.commit_hand().unwrap();
+
+        let partnerships = game.partnerships();
+        let ann_cid = partnerships.get(&PlayerId(0), &PlayerId(2)).unwrap();
+        assert_eq!(partnerships.get(&PlayerId(2), &PlayerId(0)), Some(ann_cid));
+        assert_eq!(ann_cid.hands, 2);
+        assert_eq!(ann_cid.won, 1);
+        assert_eq!(ann_cid.points, -4);
+        assert_eq!(ann_cid.success_rate(), Some(0.5));
+        assert_eq!(
+            partnerships
+                .get(&PlayerId(1), &PlayerId(3))
+                .unwrap()
+                .success_rate(),
+            None
+        );
+        assert_eq!(partnerships.get(&PlayerId(0), &PlayerId(4)), None);
+
+        let csv = partnerships.to_csv();
+        let expected = "\
+Partners,Hands,Won,Success,Points
+Ann & Bob,1,1,100%,2
+Ann & Cid,2,1,50%,-4
+";
+        assert_eq!(csv, expected);
+        assert_eq!(partnerships.to_markdown().lines().count(), 4);
+    }
+}
@@ -0,0 +1,155 @@
+use super::{players::PlayerId, rules::GameRules};
+
+/// Smallest number of players at a table.
+pub const MIN_PLAYERS: usize = 3;
+/// Largest number of players at a table.
+pub const MAX_PLAYERS: usize = 6;
+/// Number of players taking part in a hand.
+pub const ACTIVE_PLAYERS: usize = 4;
+
+/// How the players sitting out a hand are scored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SitOutScoring {
+    /// Players sitting out neither win nor lose anything.
+    #[default]
+    Nothing,
+    /// Players sitting out are settled as if they were defending the contract.
+    Defender,
+}
+
+impl SitOutScoring {
+    /// Returns the sit-out policy of a rule set.
+    #[must_use]
+    pub const fn of(rules: &GameRules) -> Self {
+        match rules {
+            GameRules::Dutch => Self::Nothing,
+            GameRules::French => Self::Defender,
+            GameRules::Custom(rule_set) => rule_set.sit_out(),
+        }
+    }
+}
+
+/// Who deals and who takes part in a hand.
+///
+/// Players sit in the order they joined the game, and the deal passes to the
+/// left after each hand. Four players take part in each hand: at a table of
+/// five the dealer sits out, and at a table of six the dealer and the player
+/// facing them sit out. At a table of three, every player takes part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seating {
+    players: usize,
+    dealer: PlayerId,
+    sitting_out: Vec<PlayerId>,
+}
+
+impl Seating {
+    #[must_use]
+    pub fn new(players: usize, dealer: PlayerId) -> Self {
+        let sitting_out = match players {
+            5 => vec![dealer.clone()],
+            6 => vec![dealer.clone(), PlayerId::new((dealer.idx() + 3) % 6)],
+            _ => Vec::new(),
+        };
+        Self {
+            players,
+            dealer,
+            sitting_out,
+        }
+    }
+
+    /// Returns the seating of a table of four, where everybody plays.
+    #[must_use]
+    pub fn four() -> Self {
+        Self::new(ACTIVE_PLAYERS, PlayerId::new(0))
+    }
+
+    /// Number of players at the table.
+    #[must_use]
+    pub const fn players(&self) -> usize {
+        self.players
+    }
+
+    #[must_use]
+    pub const fn dealer(&self) -> &PlayerId {
+        &self.dealer
+    }
+
+    /// Returns the seating of the next hand, dealt by the player on the left of
+    /// the dealer.
+    #[must_use]
+    pub fn next(&self) -> Self {
+        Self::new(self.players, left_of(&self.dealer, self.players))
+    }
+
+    #[must_use]
+    pub fn sitting_out(&self) -> &[PlayerId] {
+        &self.sitting_out
+    }
+
+    #[must_use]
+    pub fn is_sitting_out(&self, player: &PlayerId) -> bool {
+        self.sitting_out.contains(player)
+    }
+
+    /// Players taking part in the hand, in table order.
+    #[must_use]
+    pub fn active(&self) -> Vec<PlayerId> {
+        (0..self.players)
+            .map(PlayerId::new)
+            .filter(|p| !self.is_sitting_out(p))
+            .collect()
+    }
+
+    /// Returns the player who leads the first trick and speaks first during the
+    /// auction: the first player on the left of the dealer taking part in the
+    /// hand.
+    #[must_use]
+    pub fn leader(&self) -> PlayerId {
+        let mut player = left_of(&self.dealer, self.players);
+        while self.is_sitting_out(&player) {
+            player = left_of(&player, self.players);
+        }
+        player
+    }
+}
+
+const fn left_of(player: &PlayerId, players: usize) -> PlayerId {
+    PlayerId::new((player.idx() + 1) % players)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(players: &[PlayerId]) -> Vec<usize> {
+        players.iter().map(PlayerId::idx).collect()
+    }
+
+    #[test]
+    fn rotation() {
+        let four = Seating::new(4, PlayerId::new(3));
+        assert!(four.sitting_out().is_empty());
+        assert_eq!(four.leader(), PlayerId::new(0));
+        assert_eq!(four.next().dealer(), &PlayerId::new(0));
+        assert_eq!(ids(&Seating::new(3, PlayerId::new(1)).active()), [0, 1, 2]);
+
+        let mut seating = Seating::new(5, PlayerId::new(0));
+        let mut five = Vec::new();
+        for _ in 0..6 {
+            five.push(ids(seating.sitting_out()));
+            seating = seating.next();
+        }
+        assert_eq!(five, [[0], [1], [2], [3], [4], [0]]);
+        let seating = Seating::new(5, PlayerId::new(2));
+        assert_eq!(ids(&seating.active()), [0, 1, 3, 4]);
+        assert_eq!(seating.leader(), PlayerId::new(3));
+
+        let six = Seating::new(6, PlayerId::new(4));
+        assert_eq!(ids(six.sitting_out()), [4, 1]);
+        assert_eq!(ids(&six.active()), [0, 2, 3, 5]);
+        assert_eq!(six.leader(), PlayerId::new(5));
+        assert_eq!(Seating::new(6, PlayerId::new(0)).leader(), PlayerId::new(1));
+        assert_eq!(Seating::new(6, PlayerId::new(5)).leader(), PlayerId::new(0));
+    }
+}
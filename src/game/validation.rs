@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use super::{
+    Game,
+    contractors::{Contractors, ContractorsKind, ContractorsScore},
+    hand::{HandBuilder, InputError},
+    players::{PlayerId, PlayerIdAndScore},
+    rules::{GameRules, Settlement, select_rules},
+    table::{Seating, SitOutScoring},
+};
+use crate::gamemodes::TOTAL_TRICKS;
+
+/// Why a hand outcome cannot be settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsettleableReason {
+    /// The score cannot be split evenly between the defenders, or the players
+    /// scores do not add up to zero.
+    Uneven,
+    /// A score is too large to be represented.
+    Overflow,
+}
+
+/// A hand outcome whose score cannot be split between the players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsettleableOutcome {
+    pub contract_idx: usize,
+    pub gamemode_name: String,
+    pub bid: Option<i16>,
+    pub tricks: i16,
+    /// Score of each contractor for this outcome, or `None` if it overflows.
+    pub score: Option<i16>,
+    pub reason: UnsettleableReason,
+}
+
+/// Returns every outcome of a rule set that cannot be settled at a table of
+/// `players` with the given `settlement`.
+///
+/// Every contract is scored with every allowed bid and every number of tricks,
+/// from 0 to 13. An outcome is reported when its score cannot be split evenly
+/// between the defenders, when the players scores do not add up to zero, or
+/// when a score overflows. Running this before a game starts reveals house
+/// rules that would fail in the middle of the game. With
+/// [`Settlement::PerOpponent`], every outcome can be split, but the scores can
+/// still overflow.
+#[must_use]
+pub fn unsettleable_outcomes(
+    rules: &GameRules,
+    players: usize,
+    settlement: Settlement,
+) -> Vec<UnsettleableOutcome> {
+    let seating = Seating::new(players, PlayerId::new(0));
+    let sit_out = SitOutScoring::of(rules);
+    let active = seating.active();
+    let (Some(first), Some(second)) = (active.first(), active.get(1)) else {
+        return Vec::new();
+    };
+
+    let mut outcomes = Vec::new();
+    for (contract_idx, contract) in select_rules(rules).into_iter().enumerate() {
+        let contract = Arc::new(contract);
+        let bids: Vec<Option<i16>> = contract.max_bid.map_or_else(
+            || vec![None],
+            |max| (contract.min_tricks()..=max).map(Some).collect(),
+        );
+        for bid in bids {
+            for tricks in 0..=TOTAL_TRICKS {
+                let mut builder = HandBuilder::new(Arc::clone(&contract));
+                let contractors = match contract.contractors_kind {
+                    ContractorsKind::Solo => Contractors::Solo(first.clone()),
+                    ContractorsKind::Team => Contractors::Team(first.clone(), second.clone()),
+                    ContractorsKind::Other => {
+                        Contractors::Other(vec![PlayerIdAndScore::from_id(first.clone())])
+                    }
+                };
+                let independent = matches!(contractors, Contractors::Other(_));
+                let built = builder.set_contractors(contractors).and_then(|()| {
+                    if let Some(bid) = bid {
+                        builder.set_bid(bid)?;
+                    }
+                    if independent {
+                        builder.set_declarer_tricks(first.clone(), tricks)?;
+                    } else {
+                        builder.set_tricks(tricks);
+                    }
+                    builder.build()
+                });
+                let Ok(hand) = built else {
+                    continue;
+                };
+
+                let reason = match hand.get_players_score(&seating, sit_out, settlement) {
+                    Ok(scores) if scores.iter().map(|&s| i32::from(s)).sum::<i32>() == 0 => {
+                        continue;
+                    }
+                    Err(InputError::ScoreOverflow) => UnsettleableReason::Overflow,
+                    _ => UnsettleableReason::Uneven,
+                };
+                let score = match hand.get_contractors_score() {
+                    Ok(ContractorsScore::Solo(p) | ContractorsScore::Team(p, _)) => Some(p.score),
+                    Ok(ContractorsScore::Other(p)) => p.first().map(|p| p.score),
+                    Err(_) => None,
+                };
+                outcomes.push(UnsettleableOutcome {
+                    contract_idx,
+                    gamemode_name: hand.gamemode_name(),
+                    bid,
+                    tricks,
+                    score,
+                    reason,
+                });
+            }
+        }
+    }
+    outcomes
+}
+
+impl Game {
+    /// Returns every outcome of the game rule set that cannot be settled at this
+    /// table with the game settlement.
+    #[must_use]
+    pub fn unsettleable_outcomes(&self) -> Vec<UnsettleableOutcome> {
+        unsettleable_outcomes(&self.rules, self.players.list.len(), self.settlement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{players::Players, rule_set::RuleSet};
+    use crate::gamemodes::{Gamemodes, Seul};
+
+    use super::*;
+
+    #[test]
+    fn builtin_rules_settle_at_four() {
+        assert!(unsettleable_outcomes(&GameRules::Dutch, 4, Settlement::Split).is_empty());
+        assert!(unsettleable_outcomes(&GameRules::French, 4, Settlement::Split).is_empty());
+    }
+
+    #[test]
+    fn three_players_split_in_two() {
+        let outcomes = unsettleable_outcomes(&GameRules::Dutch, 3, Settlement::Split);
+        assert!(!outcomes.is_empty());
+        assert!(outcomes.iter().all(|o| o.score.is_some_and(|s| s % 2 != 0)));
+        assert!(
+            outcomes
+                .iter()
+                .all(|o| o.reason == UnsettleableReason::Uneven)
+        );
+        assert!(outcomes.iter().any(|o| o.gamemode_name == "Abondance 9"));
+        assert!(unsettleable_outcomes(&GameRules::Dutch, 3, Settlement::PerOpponent).is_empty());
+    }
+
+    #[test]
+    fn five_point_seul() {
+        let mut contracts = select_rules(&GameRules::French);
+        contracts[1].gamemode = Gamemodes::Seul(Seul::new(6, 5, 3, 8));
+        let rules = GameRules::Custom(RuleSet::new("House", contracts).unwrap());
+
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, rules).unwrap();
+        let outcomes = game.unsettleable_outcomes();
+        assert!(!outcomes.is_empty());
+        assert!(outcomes.iter().all(|o| o.contract_idx == 1));
+        assert!(outcomes.contains(&UnsettleableOutcome {
+            contract_idx: 1,
+            gamemode_name: "Seul".to_string(),
+            bid: Some(6),
+            tricks: 6,
+            score: Some(5),
+            reason: UnsettleableReason::Uneven,
+        }));
+
+        game.set_settlement(Settlement::PerOpponent);
+        assert!(game.unsettleable_outcomes().is_empty());
+    }
+
+    #[test]
+    fn overflowing_seul() {
+        let mut contracts = select_rules(&GameRules::French);
+        contracts[1].multipliers.win = 1000;
+        let rules = GameRules::Custom(RuleSet::new("House", contracts).unwrap());
+
+        assert!(unsettleable_outcomes(&rules, 4, Settlement::Split).is_empty());
+        let outcomes = unsettleable_outcomes(&rules, 4, Settlement::PerOpponent);
+        assert!(!outcomes.is_empty());
+        assert!(outcomes.iter().all(|o| o.contract_idx == 1));
+        assert!(
+            outcomes
+                .iter()
+                .all(|o| o.reason == UnsettleableReason::Overflow)
+        );
+        assert!(outcomes.contains(&UnsettleableOutcome {
+            contract_idx: 1,
+            gamemode_name: "Seul".to_string(),
+            bid: Some(6),
+            tricks: 8,
+            score: Some(12_000),
+            reason: UnsettleableReason::Overflow,
+        }));
+    }
+}
@@ -0,0 +1,89 @@
+use super::{Debug, GameResult, Score, saturate};
+
+/// A solo contract where the declarer chooses the trump and announces a high
+/// number of tricks (9 to 12 in the Dutch rules).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Abondance {
+    tricks_to_win: i16,
+    min_points: i16,
+    points_per_suppl_trick: i16,
+}
+
+impl Abondance {
+    #[must_use]
+    pub const fn new(tricks_to_win: i16, min_points: i16, points_per_suppl_trick: i16) -> Self {
+        Self {
+            tricks_to_win,
+            min_points,
+            points_per_suppl_trick,
+        }
+    }
+}
+
+impl Score for Abondance {
+    fn calculate_score(&self, tricks: i16) -> (i16, GameResult) {
+        let suppl_tricks = tricks - self.tricks_to_win;
+        let points = saturate(
+            i32::from(self.min_points)
+                + i32::from(suppl_tricks.abs()) * i32::from(self.points_per_suppl_trick),
+        );
+
+        let result = if suppl_tricks >= 0 {
+            GameResult::Win
+        } else {
+            GameResult::Lose
+        };
+        (points, result)
+    }
+
+    fn min_tricks(&self) -> i16 {
+        self.tricks_to_win
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gamemodes::Gamemodes;
+
+    use super::*;
+
+    const ABONDANCE: Abondance = Abondance {
+        tricks_to_win: 9,
+        min_points: 15,
+        points_per_suppl_trick: 3,
+    };
+
+    #[test]
+    fn win() {
+        let tricks = 9;
+        let expected_score = 15;
+
+        assert_eq!(expected_score, ABONDANCE.get_score(tricks));
+        assert_eq!(
+            expected_score,
+            Gamemodes::Abondance(ABONDANCE).get_score(tricks)
+        );
+    }
+
+    #[test]
+    fn overtricks() {
+        let tricks = 11;
+        let expected_score = 21;
+
+        assert_eq!(expected_score, ABONDANCE.get_score(tricks));
+    }
+
+    #[test]
+    fn lose() {
+        let tricks = 7;
+        let expected_score = -42;
+
+        assert_eq!(expected_score, ABONDANCE.get_score(tricks));
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(Gamemodes::Abondance(ABONDANCE).name(), "Abondance 9");
+    }
+}
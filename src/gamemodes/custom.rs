@@ -0,0 +1,325 @@
+#[cfg(feature = "serde")]
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+use std::{fmt::Debug, sync::Arc};
+
+use super::{GameResult, Score};
+
+/// A gamemode defined outside of this crate.
+///
+/// Wrap it in a [`CustomMode`] to use it in a contract like the built-in
+/// gamemodes. To save and load contracts using it, implement
+/// `SerializableGamemode` and register its type with `register_gamemode`.
+pub trait CustomGamemode: Score + Send + Sync {
+    /// Identifier of the gamemode, unique among the registered gamemodes.
+    const KIND: &'static str;
+
+    /// Name displayed for the hands of this gamemode.
+    fn name(&self) -> String;
+}
+
+/// A custom gamemode that can be saved along with its contracts.
+#[cfg(feature = "serde")]
+pub trait SerializableGamemode: CustomGamemode {
+    /// Returns the parameters of the gamemode, read back by the type
+    /// registered for its kind.
+    fn params(&self) -> serde_json::Value;
+}
+
+/// Object-safe view of a [`CustomGamemode`], which exposes its kind.
+trait DynGamemode: Score + Send + Sync {
+    fn kind(&self) -> &'static str;
+
+    fn name(&self) -> String;
+
+    /// Returns the parameters of the gamemode, if it can be serialized.
+    #[cfg(feature = "serde")]
+    fn params(&self) -> Option<serde_json::Value> {
+        None
+    }
+}
+
+impl<T: CustomGamemode> DynGamemode for T {
+    fn kind(&self) -> &'static str {
+        T::KIND
+    }
+
+    fn name(&self) -> String {
+        CustomGamemode::name(self)
+    }
+}
+
+/// A [`SerializableGamemode`] whose parameters are exposed to [`CustomMode`].
+#[cfg(feature = "serde")]
+struct Serializable<T>(T);
+
+#[cfg(feature = "serde")]
+impl<T: Debug> Debug for Serializable<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Score> Score for Serializable<T> {
+    fn min_tricks(&self) -> i16 {
+        self.0.min_tricks()
+    }
+
+    fn calculate_score(&self, tricks: i16) -> (i16, GameResult) {
+        self.0.calculate_score(tricks)
+    }
+
+    fn counted_tricks(&self, tricks: i16) -> i16 {
+        self.0.counted_tricks(tricks)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: SerializableGamemode> DynGamemode for Serializable<T> {
+    fn kind(&self) -> &'static str {
+        T::KIND
+    }
+
+    fn name(&self) -> String {
+        self.0.name()
+    }
+
+    fn params(&self) -> Option<serde_json::Value> {
+        Some(self.0.params())
+    }
+}
+
+/// A shared custom gamemode, usable as [`Gamemodes::Custom`].
+///
+/// Two custom gamemodes are equal when they have the same kind and the same
+/// debug representation. Only the gamemodes built with
+/// `CustomMode::serializable` can be serialized.
+///
+/// [`Gamemodes::Custom`]: super::Gamemodes::Custom
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(try_from = "CustomModeRepr")
+)]
+pub struct CustomMode(Arc<dyn DynGamemode>);
+
+impl CustomMode {
+    #[must_use]
+    pub fn new(gamemode: impl CustomGamemode + 'static) -> Self {
+        Self(Arc::new(gamemode))
+    }
+
+    /// Wraps a gamemode that is saved with its parameters.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn serializable(gamemode: impl SerializableGamemode + 'static) -> Self {
+        Self(Arc::new(Serializable(gamemode)))
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        self.0.kind()
+    }
+
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+}
+
+impl Score for CustomMode {
+    fn min_tricks(&self) -> i16 {
+        self.0.min_tricks()
+    }
+
+    fn calculate_score(&self, tricks: i16) -> (i16, GameResult) {
+        self.0.calculate_score(tricks)
+    }
+
+    fn counted_tricks(&self, tricks: i16) -> i16 {
+        self.0.counted_tricks(tricks)
+    }
+}
+
+impl PartialEq for CustomMode {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+            || (self.kind() == other.kind() && format!("{:?}", self.0) == format!("{:?}", other.0))
+    }
+}
+
+impl Eq for CustomMode {}
+
+#[cfg(feature = "serde")]
+type Factory = fn(serde_json::Value) -> Result<Arc<dyn DynGamemode>, serde_json::Error>;
+
+#[cfg(feature = "serde")]
+fn registry() -> &'static RwLock<HashMap<&'static str, Factory>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<&'static str, Factory>>> = OnceLock::new();
+    REGISTRY.get_or_init(RwLock::default)
+}
+
+/// Registers the type used to read back the custom gamemodes of its
+/// [`KIND`](CustomGamemode::KIND).
+///
+/// A gamemode registered under an existing kind replaces the previous one.
+#[cfg(feature = "serde")]
+#[allow(clippy::missing_panics_doc)]
+pub fn register_gamemode<T>()
+where
+    T: SerializableGamemode + serde::de::DeserializeOwned + 'static,
+{
+    let factory: Factory =
+        |params| Ok(Arc::new(Serializable(serde_json::from_value::<T>(params)?)));
+    registry()
+        .write()
+        .expect("Gamemode registry poisoned")
+        .insert(T::KIND, factory);
+}
+
+/// Serialized form of a custom gamemode: its kind and parameters.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CustomModeRepr {
+    kind: String,
+    params: serde_json::Value,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CustomModeRepr> for CustomMode {
+    type Error = String;
+
+    fn try_from(repr: CustomModeRepr) -> Result<Self, Self::Error> {
+        let factory = registry()
+            .read()
+            .map_err(|e| e.to_string())?
+            .get(repr.kind.as_str())
+            .copied()
+            .ok_or_else(|| format!("Unknown gamemode: {}", repr.kind))?;
+        factory(repr.params).map(Self).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CustomMode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let params = self.0.params().ok_or_else(|| {
+            serde::ser::Error::custom(format!("Gamemode {} is not serializable", self.kind()))
+        })?;
+        CustomModeRepr {
+            kind: self.kind().to_string(),
+            params,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        Game,
+        contractors::{Contractors, ContractorsKind},
+        players::{PlayerId, Players},
+        rule_set::RuleSet,
+        rules::{Contract, GameRules},
+    };
+    use crate::gamemodes::{Gamemodes, ResultMultipliers, TOTAL_TRICKS};
+
+    /// Twelve tricks or more, for a fixed amount of points.
+    #[derive(Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    struct Slam {
+        points: i16,
+    }
+
+    impl Score for Slam {
+        fn min_tricks(&self) -> i16 {
+            12
+        }
+
+        fn calculate_score(&self, tricks: i16) -> (i16, GameResult) {
+            match tricks {
+                TOTAL_TRICKS => (self.points, GameResult::Capot),
+                t if t >= self.min_tricks() => (self.points, GameResult::Win),
+                _ => (self.points, GameResult::Lose),
+            }
+        }
+    }
+
+    impl CustomGamemode for Slam {
+        const KIND: &'static str = "slam";
+
+        fn name(&self) -> String {
+            "Little Slam".to_string()
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl SerializableGamemode for Slam {
+        fn params(&self) -> serde_json::Value {
+            serde_json::to_value(self).unwrap()
+        }
+    }
+
+    #[test]
+    fn custom_score() {
+        let slam = Gamemodes::Custom(CustomMode::new(Slam { points: 30 }));
+        assert_eq!(slam.name(), "Little Slam");
+        assert_eq!(slam.min_tricks(), 12);
+        assert_eq!(slam.get_score(11), -60);
+        assert_eq!(slam.get_score(13), 60);
+        assert_eq!(
+            slam,
+            Gamemodes::Custom(CustomMode::new(Slam { points: 30 }))
+        );
+        assert_ne!(
+            slam,
+            Gamemodes::Custom(CustomMode::new(Slam { points: 20 }))
+        );
+    }
+
+    #[test]
+    fn custom_contract() {
+        let contract = Contract {
+            max_bid: None,
+            contractors_kind: ContractorsKind::Solo,
+            gamemode: Gamemodes::Custom(CustomMode::new(Slam { points: 30 })),
+            multipliers: ResultMultipliers::default(),
+            bid_rank: None,
+        };
+        let rule_set = RuleSet::new("Slam", vec![contract]).unwrap();
+        let players = Players::from_list(&["A", "B", "C", "D"]).unwrap();
+        let mut game = Game::new(players, GameRules::Custom(rule_set)).unwrap();
+
+        let hand = game.start_hand(0).unwrap();
+        hand.set_contractors(Contractors::Solo(PlayerId::new(2)))
+            .unwrap();
+        hand.set_tricks(12);
+        let recap = game.commit_hand().unwrap();
+        assert_eq!(recap.gamemode_name, "Little Slam");
+        assert_eq!(recap.scores, [-10, -10, 30, -10]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_registered() {
+        let unserializable = Gamemodes::Custom(CustomMode::new(Slam { points: 30 }));
+        assert!(serde_json::to_string(&unserializable).is_err());
+
+        let slam = Gamemodes::Custom(CustomMode::serializable(Slam { points: 30 }));
+        assert_eq!(slam, unserializable);
+        let json = serde_json::to_string(&slam).unwrap();
+        assert_eq!(json, r#"{"Custom":{"kind":"slam","params":{"points":30}}}"#);
+
+        let unknown = json.replace("slam", "grand-slam");
+        assert!(serde_json::from_str::<Gamemodes>(&unknown).is_err());
+
+        register_gamemode::<Slam>();
+        assert_eq!(serde_json::from_str::<Gamemodes>(&json).unwrap(), slam);
+    }
+}
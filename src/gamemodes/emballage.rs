@@ -1,6 +1,6 @@
-use super::{Debug, GameResult, Score, TOTAL_TRICKS};
+use super::{Debug, GameResult, Score, TOTAL_TRICKS, saturate};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Emballage {
     tricks_to_win: i16,
@@ -24,18 +24,19 @@ impl Score for Emballage {
         let capot = tricks == TOTAL_TRICKS;
 
         let suppl_tricks = tricks - self.tricks_to_win;
-        let mut points = self.min_points + suppl_tricks.abs() * self.points_per_suppl_trick;
+        let mut points = i32::from(self.min_points)
+            + i32::from(suppl_tricks.abs()) * i32::from(self.points_per_suppl_trick);
 
         let result = match suppl_tricks {
             0.. if capot => {
-                points -= self.points_per_suppl_trick;
+                points -= i32::from(self.points_per_suppl_trick);
                 GameResult::Capot
             }
             0.. => GameResult::Win,
             _ => GameResult::Lose,
         };
 
-        (points, result)
+        (saturate(points), result)
     }
 
     fn min_tricks(&self) -> i16 {
@@ -1,6 +1,6 @@
 use super::{Debug, GameResult, Score};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Misere {
     min_points: i16,
@@ -1,34 +1,96 @@
 // #![allow(unused)]
 
 use std::fmt::Debug;
-pub(crate) mod emballage;
-pub(crate) use emballage::Emballage;
-pub(crate) mod picolo;
-pub(crate) use picolo::Picolo;
-pub(crate) mod seul;
-pub(crate) use seul::Seul;
-pub(crate) mod misere;
-pub(crate) use misere::Misere;
+pub mod abondance;
+pub use abondance::Abondance;
+pub mod custom;
+pub use custom::{CustomGamemode, CustomMode};
+#[cfg(feature = "serde")]
+pub use custom::{SerializableGamemode, register_gamemode};
+pub mod emballage;
+pub use emballage::Emballage;
+pub mod picolo;
+pub use picolo::Picolo;
+pub mod seul;
+pub use seul::Seul;
+pub mod solo_slim;
+pub use solo_slim::SoloSlim;
+pub mod troel;
+pub use troel::Troel;
+pub mod misere;
+pub use misere::Misere;
 
 pub const TOTAL_TRICKS: i16 = 13;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameResult {
     Win,
     Lose,
     Capot,
 }
 
+/// Factors applied to the points of a gamemode, depending on the result.
+///
+/// By default, a lost contract costs twice its points and a capot pays double.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResultMultipliers {
+    pub win: i16,
+    pub lose: i16,
+    pub capot: i16,
+}
+
+impl Default for ResultMultipliers {
+    fn default() -> Self {
+        Self {
+            win: 1,
+            lose: -2,
+            capot: 2,
+        }
+    }
+}
+
+impl ResultMultipliers {
+    /// Returns the factor applied to the points of a contract with `result`.
+    #[must_use]
+    pub const fn factor(&self, result: GameResult) -> i16 {
+        match result {
+            GameResult::Win => self.win,
+            GameResult::Lose => self.lose,
+            GameResult::Capot => self.capot,
+        }
+    }
+
+    /// Returns the points multiplied by the factor of `result`, or `None` if
+    /// the product overflows.
+    #[must_use]
+    pub const fn apply(&self, points: i16, result: GameResult) -> Option<i16> {
+        points.checked_mul(self.factor(result))
+    }
+}
+
+/// Narrows points computed in `i32` to `i16`, saturating at its bounds.
+pub(crate) fn saturate(points: i32) -> i16 {
+    i16::try_from(points).unwrap_or(if points < 0 { i16::MIN } else { i16::MAX })
+}
+
 pub trait Score: Debug {
     fn min_tricks(&self) -> i16;
     fn calculate_score(&self, tricks: i16) -> (i16, GameResult);
 
+    /// Returns the tricks taken into account by [`Score::calculate_score`], for
+    /// gamemodes that cap the tricks they reward.
+    fn counted_tricks(&self, tricks: i16) -> i16 {
+        tricks
+    }
+
+    /// Returns the score for the given tricks, with the default multipliers.
+    ///
+    /// The score saturates at the bounds of `i16`.
     fn get_score(&self, tricks: i16) -> i16 {
         let (points, result) = self.calculate_score(tricks);
-        match result {
-            GameResult::Win => points,
-            GameResult::Lose => -2 * points,
-            GameResult::Capot => 2 * points,
-        }
+        points.saturating_mul(ResultMultipliers::default().factor(result))
     }
 }
 
@@ -52,19 +114,34 @@ macro_rules! score_enum {
                     )+
                }
             }
+
+            fn counted_tricks(&self, tricks: i16) -> i16 {
+                match self {
+                    $(
+                        $enum::$variant(x) => x.counted_tricks(tricks),
+                    )+
+                }
+            }
         }
     };
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Gamemodes {
     Emballage(Emballage),
     Seul(Seul),
+    Troel(Troel),
+    Abondance(Abondance),
     Picolo(Picolo),
     Misere(Misere),
     GrandeMisere(Misere),
     GrandeMisereSurTrou(Misere),
+    /// Misère where the declarers lay their cards face up after the first trick.
+    OpenMisere(Misere),
+    SoloSlim(SoloSlim),
+    /// Gamemode defined outside of this crate.
+    Custom(CustomMode),
 }
 
 impl Gamemodes {
@@ -73,10 +150,15 @@ impl Gamemodes {
         match self {
             Self::Emballage(_) => "Emballage".to_string(),
             Self::Seul(_) => "Seul".to_string(),
+            Self::Troel(_) => "Troel".to_string(),
+            Self::Abondance(x) => format!("Abondance {}", x.min_tricks()),
             Self::Picolo(_) => "Picolo".to_string(),
             Self::Misere(_) => "Petite Misere".to_string(),
             Self::GrandeMisere(_) => "Grande Misere".to_string(),
             Self::GrandeMisereSurTrou(_) => "Grande Misere sur Trou".to_string(),
+            Self::OpenMisere(_) => "Misere op Tafel".to_string(),
+            Self::SoloSlim(_) => "Solo Slim".to_string(),
+            Self::Custom(x) => x.name(),
         }
     }
 }
@@ -84,8 +166,13 @@ impl Gamemodes {
 score_enum!(Gamemodes{
     Emballage(Emballage),
     Seul(Seul),
+    Troel(Troel),
+    Abondance(Abondance),
     Picolo(Picolo),
     Misere(Misere),
     GrandeMisere(Misere),
     GrandeMisereSurTrou(Misere),
+    OpenMisere(Misere),
+    SoloSlim(SoloSlim),
+    Custom(CustomMode),
 });
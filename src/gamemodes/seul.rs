@@ -1,6 +1,6 @@
-use super::{Debug, GameResult, Score};
+use super::{Debug, GameResult, Score, saturate};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Seul {
     tricks_to_win: i16,
@@ -28,13 +28,16 @@ impl Seul {
 
 impl Score for Seul {
     fn calculate_score(&self, tricks: i16) -> (i16, GameResult) {
-        let suppl_tricks = tricks.clamp(0, self.max_tricks_allowed) - self.tricks_to_win;
+        let suppl_tricks = self.counted_tricks(tricks) - self.tricks_to_win;
+
+        let points = saturate(
+            i32::from(self.min_points)
+                + i32::from(suppl_tricks.abs()) * i32::from(self.points_per_suppl_trick),
+        );
 
         if let 0.. = suppl_tricks {
-            let points = self.min_points + suppl_tricks * self.points_per_suppl_trick;
             (points, GameResult::Win)
         } else {
-            let points = self.min_points + suppl_tricks.abs() * self.points_per_suppl_trick;
             (points, GameResult::Lose)
         }
     }
@@ -42,6 +45,11 @@ impl Score for Seul {
     fn min_tricks(&self) -> i16 {
         self.tricks_to_win
     }
+
+    /// Tricks above `max_tricks_allowed` earn no extra points.
+    fn counted_tricks(&self, tricks: i16) -> i16 {
+        tricks.min(self.max_tricks_allowed).max(0)
+    }
 }
 
 #[cfg(test)]
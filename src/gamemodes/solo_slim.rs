@@ -0,0 +1,60 @@
+use super::{Debug, GameResult, Score, TOTAL_TRICKS};
+
+/// A solo contract where the declarer must win every trick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoloSlim {
+    min_points: i16,
+}
+
+impl SoloSlim {
+    #[must_use]
+    pub const fn new(min_points: i16) -> Self {
+        Self { min_points }
+    }
+}
+
+impl Score for SoloSlim {
+    fn calculate_score(&self, tricks: i16) -> (i16, GameResult) {
+        if tricks == TOTAL_TRICKS {
+            return (self.min_points, GameResult::Win);
+        }
+        (self.min_points, GameResult::Lose)
+    }
+
+    fn min_tricks(&self) -> i16 {
+        TOTAL_TRICKS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gamemodes::Gamemodes;
+
+    use super::*;
+
+    const SOLO_SLIM: SoloSlim = SoloSlim { min_points: 60 };
+
+    #[test]
+    fn win() {
+        let tricks = 13;
+        let expected_score = 60;
+
+        assert_eq!(expected_score, SOLO_SLIM.get_score(tricks));
+        assert_eq!(
+            expected_score,
+            Gamemodes::SoloSlim(SOLO_SLIM).get_score(tricks)
+        );
+    }
+
+    #[test]
+    fn lose() {
+        let tricks = 12;
+        let expected_score = -120;
+
+        assert_eq!(expected_score, SOLO_SLIM.get_score(tricks));
+
+        let tricks = 0;
+        assert_eq!(expected_score, SOLO_SLIM.get_score(tricks));
+    }
+}
@@ -0,0 +1,81 @@
+use super::{Debug, GameResult, Score, TOTAL_TRICKS, saturate};
+
+/// A forced team contract: the player holding three aces plays with the holder
+/// of the fourth one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Troel {
+    tricks_to_win: i16,
+    min_points: i16,
+    points_per_suppl_trick: i16,
+}
+
+impl Troel {
+    #[must_use]
+    pub const fn new(tricks_to_win: i16, min_points: i16, points_per_suppl_trick: i16) -> Self {
+        Self {
+            tricks_to_win,
+            min_points,
+            points_per_suppl_trick,
+        }
+    }
+}
+
+impl Score for Troel {
+    fn calculate_score(&self, tricks: i16) -> (i16, GameResult) {
+        let suppl_tricks = tricks - self.tricks_to_win;
+        let points = saturate(
+            i32::from(self.min_points)
+                + i32::from(suppl_tricks.abs()) * i32::from(self.points_per_suppl_trick),
+        );
+
+        let result = match suppl_tricks {
+            0.. if tricks == TOTAL_TRICKS => GameResult::Capot,
+            0.. => GameResult::Win,
+            _ => GameResult::Lose,
+        };
+        (points, result)
+    }
+
+    fn min_tricks(&self) -> i16 {
+        self.tricks_to_win
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gamemodes::Gamemodes;
+
+    use super::*;
+
+    const TROEL: Troel = Troel {
+        tricks_to_win: 8,
+        min_points: 4,
+        points_per_suppl_trick: 2,
+    };
+
+    #[test]
+    fn win() {
+        let tricks = 9;
+        let expected_score = 6;
+
+        assert_eq!(expected_score, TROEL.get_score(tricks));
+        assert_eq!(expected_score, Gamemodes::Troel(TROEL).get_score(tricks));
+    }
+
+    #[test]
+    fn lose() {
+        let tricks = 7;
+        let expected_score = -12;
+
+        assert_eq!(expected_score, TROEL.get_score(tricks));
+    }
+
+    #[test]
+    fn capot() {
+        let tricks = 13;
+        let expected_score = 28;
+
+        assert_eq!(expected_score, TROEL.get_score(tricks));
+    }
+}